@@ -1,5 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use alpenglow_stateright::*;
+use stateright::{Checker, Model};
 use std::collections::HashMap;
 
 fn create_benchmark_model(nodes: u32) -> AlpenglowState {
@@ -43,7 +44,8 @@ fn benchmark_state_generation(c: &mut Criterion) {
         let initial_state = model.init_states()[0].clone();
         
         b.iter(|| {
-            let actions = model.actions(black_box(&initial_state));
+            let mut actions = Vec::new();
+            model.actions(black_box(&initial_state), &mut actions);
             for action in actions.into_iter().take(10) {
                 let _ = model.next_state(&initial_state, action);
             }
@@ -80,7 +82,6 @@ fn benchmark_certificate_generation(c: &mut Criterion) {
                 slot: 1,
                 block: 1,
                 path: VotePath::Fast,
-                timestamp: 0,
                 stake: 250,
             };
             state.votes.get_mut(&node).unwrap().get_mut(&1).unwrap().push(vote);
@@ -2,8 +2,8 @@
 // This example demonstrates sophisticated Byzantine attacks including coordinated coalitions,
 // network manipulation, and economic incentive-based attacks.
 
-use alpenglow_stateright::{*, lib_advanced::*, model_advanced::*};
-use std::collections::HashMap;
+use alpenglow_stateright::*;
+use std::collections::{HashMap, HashSet};
 use stateright::*;
 
 /// Demonstrates coordinated Byzantine coalition attack
@@ -19,31 +19,14 @@ fn coalition_attack_example() {
         })
         .collect();
     
-    let model = AlpenglowAdvancedModel::new(
-        nodes.clone(),
-        stake_distribution,
-        NetworkConditions {
-            base_latency: 100,
-            jitter: 50,
-            packet_loss_rate: 0.02,
-            corruption_rate: 0.01,
-        },
-        EconomicParams {
-            base_reward: 1000,
-            penalty_multiplier: 3.0,
-            reward_pool: 500_000,
-            inflation_rate: 0.03,
-            validator_reward_share: 0.7,
-        },
-    );
-    
+    let model = AlpenglowState::new(nodes.clone(), stake_distribution);
     let mut state = model.init_states()[0].clone();
     
     // Form a Byzantine coalition
     state.status.insert(1, NodeStatus::Byzantine(ByzantineStrategy::Equivocation));
     state.status.insert(2, NodeStatus::Byzantine(ByzantineStrategy::SelectiveEquivocation {
-        target_nodes: vec![3, 4, 5].into_iter().collect(),
-        probability: 0.8,
+        min_stake_threshold: 100_000,
+        target_slots: vec![1, 2, 3],
     }));
     
     println!("Initial state:");
@@ -56,7 +39,7 @@ fn coalition_attack_example() {
     model.actions(&state, &mut actions);
     
     if let Some(coalition_action) = actions.iter().find(|action| {
-        matches!(action, AlpenglowAdvancedAction::FormCoalition { .. })
+        matches!(action, AlpenglowAction::FormCoalition { .. })
     }) {
         println!("  Forming Byzantine coalition...");
         state = model.next_state(&state, coalition_action.clone()).unwrap();
@@ -72,33 +55,46 @@ fn coalition_attack_example() {
         
         // Look for coordinated attack actions
         if let Some(attack_action) = round_actions.iter().find(|action| {
-            matches!(action, AlpenglowAdvancedAction::CoordinateAttack { .. })
+            matches!(action, AlpenglowAction::CoordinateAttack { .. })
         }) {
             println!("  Executing coordinated attack on slot {}", state.current_slot);
             state = model.next_state(&state, attack_action.clone()).unwrap();
-            
-            // Check for detected violations
-            let violations: Vec<_> = state.nodes.iter()
-                .flat_map(|&node| state.detect_byzantine_behavior(node, state.current_slot))
-                .collect();
-            
-            println!("  Detected {} Byzantine violations", violations.len());
-            
-            // Check economic penalties
-            let total_penalties: StakeAmount = state.economic_state.penalties.values().sum();
-            println!("  Total penalties applied: {}", total_penalties);
         }
         
+        // Byzantine members cast their votes
+        for action in round_actions.iter().filter(|action| {
+            matches!(action, AlpenglowAction::ByzantineVote { .. })
+        }) {
+            state = model.next_state(&state, action.clone()).unwrap();
+        }
+        
+        // Check for detected double votes
+        let violations = state.nodes.iter()
+            .filter(|&&node| {
+                let votes = state.votes[&node].get(&state.current_slot).cloned().unwrap_or_default();
+                votes.iter().any(|a| votes.iter().any(|b| state.detect_double_voting(a, b).is_some()))
+            })
+            .count();
+        println!("  Detected {} Byzantine violations", violations);
+        
         // Advance time
-        state = model.next_state(&state, AlpenglowAdvancedAction::AdvanceTime { delta: 500 }).unwrap();
+        state = model.next_state(&state, AlpenglowAction::AdvanceTime { delta: 500 }).unwrap();
     }
     
     println!("\n=== Attack Results ===");
     println!("Final slot: {}", state.current_slot);
     println!("Certificates created: {}", state.certificates.len());
     println!("Skip certificates: {}", state.skip_certs.len());
-    println!("Safety violated: {}", state.is_safety_violated());
-    println!("Liveness violated: {}", state.is_liveness_violated(3));
+    println!("Safety violated: {}", property_violated(&model, &state, "stake_weighted_safety"));
+    println!("Liveness violated: {}", property_violated(&model, &state, "progress"));
+}
+
+/// Whether the model property `name` fails on `state`
+fn property_violated(model: &AlpenglowState, state: &AlpenglowState, name: &str) -> bool {
+    model.properties()
+        .iter()
+        .find(|property| property.name == name)
+        .is_some_and(|property| !(property.condition)(model, state))
 }
 
 /// Demonstrates network partition attack
@@ -110,27 +106,18 @@ fn network_partition_example() {
         .map(|&node| (node, 100_000))
         .collect();
     
-    let model = AlpenglowAdvancedModel::new(
-        nodes.clone(),
-        stake_distribution,
-        NetworkConditions {
-            base_latency: 200,
-            jitter: 100,
-            packet_loss_rate: 0.15, // High packet loss
-            corruption_rate: 0.05,  // High corruption
-        },
-        EconomicParams::default(),
-    );
-    
+    let model = AlpenglowState::new(nodes.clone(), stake_distribution);
     let mut state = model.init_states()[0].clone();
+    state.network_state.latency_model = LatencyModel::Uniform { min_ms: 100, max_ms: 300 };
+    state.network_state.packet_loss_rate = 0.15; // High packet loss
     
     // Create network partition
-    let partition_a: std::collections::HashSet<NodeId> = vec![1, 2, 3].into_iter().collect();
-    let partition_b: std::collections::HashSet<NodeId> = vec![4, 5, 6].into_iter().collect();
+    let partition_a: HashSet<NodeId> = [1, 2, 3].into_iter().collect();
+    let partition_b: HashSet<NodeId> = [4, 5, 6].into_iter().collect();
     
-    let partition_action = AlpenglowAdvancedAction::CreatePartition {
-        partition_type: PartitionType::Complete,
-        nodes: vec![partition_a.clone(), partition_b.clone()],
+    let partition_action = AlpenglowAction::NetworkPartition {
+        nodes_a: partition_a.clone(),
+        nodes_b: partition_b.clone(),
     };
     
     println!("Creating network partition:");
@@ -140,24 +127,24 @@ fn network_partition_example() {
     state = model.next_state(&state, partition_action).unwrap();
     
     // Test communication
-    let can_communicate_within = state.can_nodes_communicate(1, 2);
-    let can_communicate_across = state.can_nodes_communicate(1, 4);
+    let can_communicate_within = state.can_node_communicate(1, 2);
+    let can_communicate_across = state.can_node_communicate(1, 4);
     
     println!("  Communication within partition: {}", can_communicate_within);
     println!("  Communication across partition: {}", can_communicate_across);
     
     // Run consensus with partition
-    for round in 1..=5 {
+    for _ in 1..=5 {
         let mut actions = Vec::new();
         model.actions(&state, &mut actions);
         
-        // Apply first available action
-        if let Some(action) = actions.first() {
+        // Apply first available vote
+        if let Some(action) = actions.iter().find(|action| matches!(action, AlpenglowAction::Vote { .. })) {
             state = model.next_state(&state, action.clone()).unwrap();
         }
         
         // Advance time
-        state = model.next_state(&state, AlpenglowAdvancedAction::AdvanceTime { delta: 300 }).unwrap();
+        state = model.next_state(&state, AlpenglowAction::AdvanceTime { delta: 300 }).unwrap();
     }
     
     println!("Partition results:");
@@ -171,7 +158,7 @@ fn economic_attack_example() {
     println!("\n=== Economic Attack Example ===");
     
     let nodes = vec![1, 2, 3, 4, 5];
-    let stake_distribution: HashMap<NodeId, StakeAmount> = vec![
+    let stake_distribution: HashMap<NodeId, StakeAmount> = [
         (1, 200_000), // High stake attacker
         (2, 100_000),
         (3, 100_000),
@@ -179,36 +166,20 @@ fn economic_attack_example() {
         (5, 100_000),
     ].into_iter().collect();
     
-    let model = AlpenglowAdvancedModel::new(
-        nodes.clone(),
-        stake_distribution,
-        NetworkConditions::default(),
-        EconomicParams {
-            base_reward: 2000,
-            penalty_multiplier: 2.5,
-            reward_pool: 1_000_000,
-            inflation_rate: 0.05,
-            validator_reward_share: 0.8,
-        },
-    );
-    
+    let model = AlpenglowState::new(nodes.clone(), stake_distribution);
     let mut state = model.init_states()[0].clone();
     
     // Set up economically motivated attacker
     state.status.insert(1, NodeStatus::Byzantine(ByzantineStrategy::StakeBasedAttack {
-        target_stake_threshold: 150_000,
-        attack_probability: 0.9,
-    }));
-    
-    // Set up rational validator
-    state.status.insert(2, NodeStatus::Economically(EconomicallyMotivated::RationalValidator {
-        profit_threshold: 1500.0,
+        reserve_stake_for_critical_slots: true,
+        activation_threshold: 150_000,
+        min_profit_margin: 1_500,
     }));
     
     println!("Economic setup:");
-    println!("  Reward pool: {}", state.economic_state.reward_pool);
-    println!("  High stake attacker (node 1): {} stake", state.effective_stake(1));
-    println!("  Rational validator (node 2): {} stake", state.effective_stake(2));
+    println!("  Reward pool: {}", state.economic_state.rewards_pool);
+    println!("  High stake attacker (node 1): {} stake", state.stake_distribution[&1]);
+    println!("  Honest validator (node 2): {} stake", state.stake_distribution[&2]);
     
     // Simulate economic attacks
     for round in 1..=4 {
@@ -217,51 +188,40 @@ fn economic_attack_example() {
         let mut actions = Vec::new();
         model.actions(&state, &mut actions);
         
-        // Check economic incentives for different actions
-        for &node in &[1, 2] {
-            let vote_incentive = state.calculate_economic_incentive(
-                node,
-                &AlpenglowAdvancedAction::Vote {
-                    node,
-                    slot: state.current_slot,
-                    block: state.current_slot,
-                    path: VotePath::Fast,
-                }
-            );
-            
-            println!("  Node {} vote incentive: {}", node, vote_incentive);
-        }
-        
-        // Apply reward distribution
-        if let Some(reward_action) = actions.iter().find(|action| {
-            matches!(action, AlpenglowAdvancedAction::DistributeRewards { .. })
+        // Attacker votes according to its strategy
+        if let Some(action) = actions.iter().find(|action| {
+            matches!(action, AlpenglowAction::ByzantineVote { node: 1, .. })
         }) {
-            state = model.next_state(&state, reward_action.clone()).unwrap();
+            state = model.next_state(&state, action.clone()).unwrap();
         }
         
         // Apply slashing if violations detected
-        for slashing_action in actions.iter().filter(|action| {
-            matches!(action, AlpenglowAdvancedAction::ApplySlashing { .. })
-        }).take(2) {
-            state = model.next_state(&state, slashing_action.clone()).unwrap();
+        let attacker_votes = state.votes[&1].get(&state.current_slot).cloned().unwrap_or_default();
+        let evidence = attacker_votes.iter()
+            .flat_map(|a| attacker_votes.iter().map(move |b| (a, b)))
+            .find_map(|(a, b)| state.detect_double_voting(a, b));
+        if let Some(evidence) = evidence {
+            state = model.next_state(&state, AlpenglowAction::SlashValidator { evidence }).unwrap();
         }
         
+        // Apply reward distribution
+        let participating: Vec<NodeId> = nodes.iter().copied().filter(|&n| n != 1).collect();
+        let rewards = state.calculate_epoch_rewards(round, &participating);
+        state = model.next_state(&state, AlpenglowAction::DistributeRewards { epoch: round, rewards }).unwrap();
+        
         // Advance time
-        state = model.next_state(&state, AlpenglowAdvancedAction::AdvanceTime { delta: 400 }).unwrap();
+        state = model.next_state(&state, AlpenglowAction::AdvanceTime { delta: 400 }).unwrap();
     }
     
     println!("\nEconomic results:");
     for &node in &nodes {
-        let rewards = state.economic_state.rewards.get(&node).unwrap_or(&0);
-        let penalties = state.economic_state.penalties.get(&node).unwrap_or(&0);
-        let net_gain = (*rewards as i64) - (*penalties as i64);
-        println!("  Node {}: rewards={}, penalties={}, net={}", node, rewards, penalties, net_gain);
+        let original = state.stake_distribution[&node];
+        let balance = state.economic_state.validator_balances.get(&node).copied().unwrap_or(0);
+        let net_gain = (balance as i64) - (original as i64);
+        println!("  Node {}: balance={}, net={}", node, balance, net_gain);
     }
     
-    let total_slashing: StakeAmount = state.economic_state.slashing_conditions.iter()
-        .map(|slash| slash.penalty_amount)
-        .sum();
-    println!("  Total slashing applied: {}", total_slashing);
+    println!("  Total slashing applied: {}", state.economic_state.total_slashed);
 }
 
 /// Demonstrates adaptive Byzantine strategy
@@ -273,20 +233,14 @@ fn adaptive_strategy_example() {
         .map(|&node| (node, 100_000))
         .collect();
     
-    let model = AlpenglowAdvancedModel::new(
-        nodes.clone(),
-        stake_distribution,
-        NetworkConditions::default(),
-        EconomicParams::default(),
-    );
-    
+    let model = AlpenglowState::new(nodes.clone(), stake_distribution);
     let mut state = model.init_states()[0].clone();
     
     // Set up adaptive Byzantine node
-    state.status.insert(1, NodeStatus::Byzantine(ByzantineStrategy::AdaptiveStrategy {
-        success_threshold: 0.6,
-        adaptation_rate: 0.2,
-        current_strategy: Box::new(ByzantineStrategy::Equivocation),
+    state.status.insert(1, NodeStatus::Byzantine(ByzantineStrategy::AdaptiveBehavior {
+        primary_strategy: Box::new(ByzantineStrategy::Equivocation),
+        fallback_strategy: Box::new(ByzantineStrategy::WithholdVotes),
+        adaptation_threshold: 2,
     }));
     
     println!("Adaptive strategy simulation:");
@@ -299,44 +253,37 @@ fn adaptive_strategy_example() {
         model.actions(&state, &mut actions);
         
         // Execute Byzantine votes
-        for action in actions.iter().filter(|action| {
-            matches!(action, AlpenglowAdvancedAction::ByzantineVote { node: 1, .. })
-        }).take(1) {
+        if let Some(action) = actions.iter().find(|action| {
+            matches!(action, AlpenglowAction::ByzantineVote { node: 1, .. })
+        }) {
             state = model.next_state(&state, action.clone()).unwrap();
         }
         
         // Check detection
-        let violations = state.detect_byzantine_behavior(1, state.current_slot);
+        let votes = state.votes[&1].get(&state.current_slot).cloned().unwrap_or_default();
+        let violations: Vec<_> = votes.iter()
+            .flat_map(|a| votes.iter().map(move |b| (a, b)))
+            .filter_map(|(a, b)| state.detect_double_voting(a, b))
+            .collect();
         let detected = !violations.is_empty();
         
         println!("  Byzantine behavior detected: {}", detected);
         
-        if detected {
-            println!("  Violations: {:?}", violations.iter().map(|v| format!("{:?}", v)).collect::<Vec<_>>());
-            
-            // Apply penalties
-            for violation in violations {
-                let penalty = state.effective_stake(1) / 10;
-                let slashing_action = AlpenglowAdvancedAction::ApplySlashing {
-                    node: 1,
-                    violation,
-                    penalty,
-                };
-                state = model.next_state(&state, slashing_action).unwrap();
-            }
+        if let Some(evidence) = violations.into_iter().next() {
+            state = model.next_state(&state, AlpenglowAction::SlashValidator { evidence }).unwrap();
         }
         
-        // The adaptive strategy should change based on success/failure
-        // (In a full implementation, this would be handled automatically)
+        // Timeouts push the adaptive node towards its fallback strategy
+        state = model.next_state(&state, AlpenglowAction::Timeout { node: 1, slot: state.current_slot }).unwrap();
         
         // Advance time and slot
-        state = model.next_state(&state, AlpenglowAdvancedAction::AdvanceTime { delta: 600 }).unwrap();
+        state = model.next_state(&state, AlpenglowAction::AdvanceTime { delta: 600 }).unwrap();
     }
     
     println!("\nAdaptive strategy results:");
-    let final_penalties = state.economic_state.penalties.get(&1).unwrap_or(&0);
-    let total_violations = state.economic_state.slashing_conditions.len();
-    println!("  Final penalties for adaptive node: {}", final_penalties);
+    let final_balance = state.economic_state.validator_balances.get(&1).unwrap_or(&0);
+    let total_violations = state.economic_state.slashing_evidence.len();
+    println!("  Final balance for adaptive node: {}", final_balance);
     println!("  Total violations recorded: {}", total_violations);
 }
 
@@ -356,7 +303,7 @@ fn main() {
     println!("3. Economic incentive-based attacks and slashing");
     println!("4. Adaptive Byzantine strategies that evolve over time");
     println!();
-    println!("The advanced model provides comprehensive Byzantine fault tolerance");
+    println!("The model provides comprehensive Byzantine fault tolerance");
     println!("analysis with realistic network conditions and economic incentives.");
 }
 
@@ -371,12 +318,7 @@ mod example_tests {
             .map(|&node| (node, 100_000))
             .collect();
         
-        let model = AlpenglowAdvancedModel::new(
-            nodes.clone(),
-            stake_distribution,
-            NetworkConditions::default(),
-            EconomicParams::default(),
-        );
+        let model = AlpenglowState::new(nodes.clone(), stake_distribution);
         
         let state = &model.init_states()[0];
         assert_eq!(state.nodes.len(), 5);
@@ -385,28 +327,18 @@ mod example_tests {
     
     #[test]
     fn test_network_conditions_setup() {
-        let conditions = NetworkConditions {
-            base_latency: 200,
-            jitter: 100,
-            packet_loss_rate: 0.15,
-            corruption_rate: 0.05,
-        };
+        let mut state = AlpenglowState::new(vec![1, 2], HashMap::from([(1, 100), (2, 100)]));
+        state.network_state.latency_model = LatencyModel::Uniform { min_ms: 100, max_ms: 300 };
+        state.network_state.packet_loss_rate = 0.15;
         
-        assert_eq!(conditions.base_latency, 200);
-        assert_eq!(conditions.packet_loss_rate, 0.15);
+        assert_eq!(state.network_state.packet_loss_rate, 0.15);
     }
     
     #[test]
     fn test_economic_params_setup() {
-        let params = EconomicParams {
-            base_reward: 2000,
-            penalty_multiplier: 2.5,
-            reward_pool: 1_000_000,
-            inflation_rate: 0.05,
-            validator_reward_share: 0.8,
-        };
+        let state = AlpenglowState::new(vec![1, 2], HashMap::from([(1, 100), (2, 100)]));
         
-        assert_eq!(params.base_reward, 2000);
-        assert_eq!(params.reward_pool, 1_000_000);
+        assert_eq!(state.economic_state.rewards_pool, 2000);
+        assert_eq!(state.economic_state.total_slashed, 0);
     }
-}
\ No newline at end of file
+}
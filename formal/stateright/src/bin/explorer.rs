@@ -4,7 +4,6 @@ use std::collections::HashMap;
 
 /// Interactive model explorer for Alpenglow consensus protocol
 /// Run with: cargo run --bin explorer
-
 fn main() {
     println!("🔥 Alpenglow Consensus Protocol - Model Explorer");
     println!("================================================");
@@ -1,14 +1,38 @@
 use stateright::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use serde::{Deserialize, Serialize};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 
-/// Alpenglow Consensus Protocol - Enhanced Stateright Model
-/// Implements Votor dual-path consensus with improved stake weighting and Byzantine behaviors
+// Alpenglow Consensus Protocol - Enhanced Stateright Model
+// Implements Votor dual-path consensus with improved stake weighting and Byzantine behaviors
 
 pub mod scalability;
 pub use scalability::*;
 
+pub mod rotor_analysis;
+pub use rotor_analysis::*;
+
+pub mod topology;
+pub use topology::*;
+
+pub mod metrics;
+pub use metrics::*;
+
+pub mod failure_detector;
+pub use failure_detector::*;
+
+pub mod adversary_search;
+pub use adversary_search::*;
+
+pub mod attack_report;
+pub use attack_report::*;
+
+pub mod resilience_sweep;
+pub use resilience_sweep::*;
+
 pub type NodeId = u32;
 pub type Slot = u32;
 pub type BlockId = u32;
@@ -49,6 +73,28 @@ pub struct RelayNode {
     pub assigned_chunks: Vec<u32>,
 }
 
+/// A relay role for one block, as assigned by that block's leader
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelayAssignment {
+    pub slot: Slot,
+    pub block_id: BlockId,
+    pub leader: NodeId,
+    pub relay: RelayNode,
+}
+
+/// How Rotor draws one relay per chunk from the stake distribution
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Hash)]
+pub enum RelaySampling {
+    /// Independent stake-weighted draw per chunk
+    WithReplacement,
+    /// Stake-weighted draws without repeating a relay until every node has been used
+    WithoutReplacement,
+    /// Partition sampling: floor(stake share * chunks) fixed slots per node,
+    /// remaining slots sampled from stake-sized bins
+    #[default]
+    PartitionSampling,
+}
+
 // Leader rotation and windowing structures
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Hash)]
 pub struct WindowInfo {
@@ -178,13 +224,45 @@ pub struct AlpenglowState {
     pub economic_state: EconomicState,
     // Rotor erasure coding
     pub erasure_coded_blocks: HashMap<BlockId, ErasureCodedBlock>,
-    pub relay_assignments: HashMap<NodeId, RelayNode>,
+    pub relay_assignments: HashMap<(BlockId, NodeId), RelayAssignment>, // (block, relay) -> assignment
+    pub relay_sampling: RelaySampling,
     pub chunk_availability: HashMap<(BlockId, u32), HashSet<NodeId>>, // (block, chunk) -> nodes that have it
     // Leader rotation and windowing
     pub current_window: WindowInfo,
     pub leader_rotation: LeaderRotation,
-    pub finalization_times: HashMap<Slot, Timestamp>, // Track actual finalization times
+    pub slot_start_times: BTreeMap<Slot, Timestamp>,
+    pub proposals: BTreeMap<Slot, Proposal>,
     pub view: u64,
+    pub failure_detector: Option<FailureDetector>, // heartbeat-based peer suspicion
+    pub crash_budget: f64, // fraction of total stake that may be crashed at once
+    pub vote_logs: HashMap<NodeId, Vec<Vote>>, // own votes persisted by crashed nodes
+    #[serde(skip)]
+    pub metrics: MetricsHandle, // run analytics, outside the explored state
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SlotOutcome {
+    Finalized,
+    Skipped,
+}
+
+/// Network faults in effect when a slot was finalized or skipped
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SlotFaultReport {
+    pub slot: Slot,
+    pub outcome: SlotOutcome,
+    pub resolved_at: Timestamp,
+    pub active_failures: Vec<FailureType>,
+    pub partition: Option<NetworkPartition>,
+}
+
+/// What honest nodes received from a slot's leader; `blocks` stays empty when the
+/// leader withheld its proposal and holds two blocks when it equivocated
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Proposal {
+    pub leader: NodeId,
+    pub blocks: Vec<Block>,
+    pub arrives_at: Timestamp, // honest nodes cannot vote on the proposal before this
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -252,6 +330,40 @@ pub enum ByzantineStrategy {
         /// Economic incentive threshold
         min_profit_margin: StakeAmount,
     },
+    RelayAttack {
+        /// Misbehavior applied whenever the node forwards Rotor chunks
+        behavior: RelayAttackType,
+    },
+    LeaderAttack {
+        /// Misbehavior applied whenever the node leads a slot
+        behavior: LeaderAttackType,
+    },
+}
+
+/// How a Byzantine relay mishandles the chunks it is asked to forward
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RelayAttackType {
+    /// Never forward any chunk
+    DropChunks,
+    /// Forward only to the listed nodes
+    ForwardToSubset { recipients: Vec<NodeId> },
+    /// Hold chunks back before forwarding them
+    DelayForwarding { delay_ms: Timestamp },
+    /// Forward chunks with tampered data
+    CorruptChunks,
+}
+
+/// How a Byzantine leader mishandles the slots it leads
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum LeaderAttackType {
+    /// Never propose, leaving honest nodes to time out
+    WithholdProposal,
+    /// Propose a second block for the same slot and send its chunks to half the relays
+    EquivocateProposal,
+    /// Build on a parent that is neither genesis nor notarized
+    UnreadyParent,
+    /// Propose this long after the slot starts
+    LateProposal { delay_ms: Timestamp },
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -267,7 +379,7 @@ pub enum CoalitionAttackType {
     /// Coalition targets specific high-value slots for maximum disruption
     StrategicTargeting {
         high_priority_slots: Vec<Slot>,
-        disruption_threshold: f64, // Minimum disruption probability
+        disruption_threshold: f64, // disruption probability the coalition must exceed
     },
     /// Coalition attempts to manipulate certificate generation
     CertificateManipulation {
@@ -317,13 +429,33 @@ pub struct SkipCertificate {
     pub total_stake: StakeAmount,
 }
 
+/// Nodes split into groups that cannot reach each other, except along `one_way` directions.
+/// Nodes outside every group are unaffected.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NetworkPartition {
-    pub partition_a: HashSet<NodeId>,
-    pub partition_b: HashSet<NodeId>,
+    pub groups: Vec<HashSet<NodeId>>,
+    pub one_way: Vec<(usize, usize)>, // (from group, to group) pairs that still deliver
     pub started_at: Timestamp,
 }
 
+impl NetworkPartition {
+    pub fn new(groups: Vec<HashSet<NodeId>>, started_at: Timestamp) -> Self {
+        Self { groups, one_way: Vec::new(), started_at }
+    }
+    
+    pub fn group_of(&self, node: NodeId) -> Option<usize> {
+        self.groups.iter().position(|group| group.contains(&node))
+    }
+    
+    /// Whether messages from `from` reach `to`
+    pub fn allows(&self, from: NodeId, to: NodeId) -> bool {
+        match (self.group_of(from), self.group_of(to)) {
+            (Some(a), Some(b)) => a == b || self.one_way.contains(&(a, b)),
+            _ => true,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ByzantineCoalition {
     pub members: Vec<NodeId>,
@@ -365,12 +497,28 @@ pub enum AttackPhase {
     Adaptation,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct AttackMetrics {
     pub slots_disrupted: u32,
     pub certificates_prevented: u32,
     pub timeouts_caused: u32,
     pub economic_damage: StakeAmount,
+    // Rotor relay misbehavior, counted per (chunk, recipient)
+    pub chunks_withheld: u32,
+    pub chunks_delayed: u32,
+    pub chunks_corrupted: u32,
+}
+
+impl AttackMetrics {
+    pub fn accumulate(&mut self, other: &AttackMetrics) {
+        self.slots_disrupted += other.slots_disrupted;
+        self.certificates_prevented += other.certificates_prevented;
+        self.timeouts_caused += other.timeouts_caused;
+        self.economic_damage += other.economic_damage;
+        self.chunks_withheld += other.chunks_withheld;
+        self.chunks_delayed += other.chunks_delayed;
+        self.chunks_corrupted += other.chunks_corrupted;
+    }
 }
 
 /// Network simulation structures for realistic modeling
@@ -379,15 +527,155 @@ pub struct NetworkSimulationState {
     pub latency_model: LatencyModel,
     pub packet_loss_rate: f64, // 0.0 to 1.0
     pub bandwidth_limits: HashMap<(NodeId, NodeId), Bandwidth>,
+    pub node_bandwidth: HashMap<NodeId, Bandwidth>, // uplink capacity per sender
+    pub uplink_free_at: HashMap<NodeId, Timestamp>, // when each sender's uplink finishes its queued chunks
     pub congestion_state: CongestionState,
     pub failure_injections: Vec<NetworkFailure>,
+    pub randomness: NetworkRandomness,
+    pub seed: u64, // PRNG seed for `NetworkRandomness::Seeded` runs
+    pub link_queues: HashMap<(NodeId, NodeId), Vec<LinkTransmission>>, // backlog of each throttled link
+    pub reliable_delivery: Option<RetransmissionPolicy>, // acks and retransmission for Critical messages
+    pub fault_schedule: FaultSchedule,
+    pub faults_released: usize, // schedule events already moved into `failure_injections`
+    pub partial_synchrony: Option<PartialSynchrony>,
+}
+
+/// Partial synchrony: before `gst` the adversary may hold any message, after it every
+/// message between honest nodes arrives within `delta`.
+///
+/// Partitions and link or node failures count as adversarial delay in this mode: honest
+/// messages they would drop are held and arrive by their deadline, even while the fault is
+/// still active. Only traffic to or from non-honest nodes is still cut.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PartialSynchrony {
+    pub gst: Timestamp,
+    pub delta: Timestamp,
+    pub slot_bound: Timestamp, // time after max(slot start, GST) by which a slot must be finalized or skipped
+}
+
+impl PartialSynchrony {
+    pub fn new(gst: Timestamp) -> Self {
+        Self {
+            gst,
+            delta: DELTA_80_MS,
+            // Block, votes and certificate each take at most one Δ
+            slot_bound: 3 * DELTA_80_MS,
+        }
+    }
+    
+    /// Latest delivery time for an honest message sent at `send_time`
+    pub fn delivery_deadline(&self, send_time: Timestamp) -> Timestamp {
+        send_time.max(self.gst) + self.delta
+    }
+}
+
+/// Timed network failures loaded with a scenario; each one is injected at its `start_time`
+/// and removed once its `duration` has passed
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FaultSchedule {
+    pub events: Vec<NetworkFailure>,
 }
 
+impl FaultSchedule {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// A message queued on, or being sent over, a throttled link
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LinkTransmission {
+    pub message_id: u64,
+    pub priority: MessagePriority,
+    pub ready: Timestamp,         // when the sender handed it to the link
+    pub duration: Timestamp,      // wire size over link bandwidth
+    pub start: Option<Timestamp>, // planned start; fixed once reached
+    pub finish: Timestamp,        // end of transmission already reflected in the delivery time
+}
+
+/// Where packet loss and random latency come from
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Hash)]
+pub enum NetworkRandomness {
+    /// Draws from a PRNG keyed by `seed` and the message id; reproducible for a given seed
+    #[default]
+    Seeded,
+    /// Messages are sent at the lowest latency and the checker branches on loss and extra delay
+    Nondeterministic,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct MessageQueue {
     pub pending_messages: Vec<PendingMessage>,
-    pub delivered_messages: Vec<DeliveredMessage>,
     pub message_counter: u64,
+    pub unacked: BTreeMap<u64, UnackedMessage>, // reliable messages by original id
+}
+
+/// Acknowledged delivery with retransmission for Critical messages
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetransmissionPolicy {
+    pub ack_timeout: Timestamp, // wait before the first retransmission
+    pub backoff_factor: u64,    // each further wait is this many times longer
+    pub max_retries: u32,
+}
+
+impl Default for RetransmissionPolicy {
+    fn default() -> Self {
+        Self {
+            ack_timeout: 2 * DELTA_80_MS,
+            backoff_factor: 2,
+            max_retries: 5,
+        }
+    }
+}
+
+/// A reliable message the sender keeps until it is acknowledged or retries run out
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnackedMessage {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub content: MessageContent,
+    pub priority: MessagePriority,
+    pub retry_count: u32,
+    pub retransmit_at: Timestamp,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReliableDeliveryStats {
+    pub messages_sent: u32, // distinct reliable messages, not counting retransmissions
+    pub retransmissions: u32,
+    pub acks_sent: u32,
+    pub acknowledged: u32,
+    pub abandoned: u32, // gave up after `max_retries`
+}
+
+/// A step in the life of a reliable message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReliableEvent {
+    Sent,
+    Retransmitted,
+    AckSent,
+    Acknowledged,
+    Abandoned,
+}
+
+impl ReliableDeliveryStats {
+    pub fn record(&mut self, event: ReliableEvent) {
+        match event {
+            ReliableEvent::Sent => self.messages_sent += 1,
+            ReliableEvent::Retransmitted => self.retransmissions += 1,
+            ReliableEvent::AckSent => self.acks_sent += 1,
+            ReliableEvent::Acknowledged => self.acknowledged += 1,
+            ReliableEvent::Abandoned => self.abandoned += 1,
+        }
+    }
+    
+    /// Extra transmissions, retransmits plus acks, per reliable message
+    pub fn overhead(&self) -> f64 {
+        if self.messages_sent == 0 {
+            return 0.0;
+        }
+        (self.retransmissions + self.acks_sent) as f64 / self.messages_sent as f64
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -400,6 +688,9 @@ pub struct PendingMessage {
     pub scheduled_delivery_time: Timestamp,
     pub priority: MessagePriority,
     pub retry_count: u32,
+    pub latency_slack: Timestamp, // extra delay a nondeterministic run may still add
+    pub reliable_id: Option<u64>, // original id, when the receiver must acknowledge
+    pub deliver_by: Option<Timestamp>, // partial-synchrony deadline for messages between honest nodes
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -419,10 +710,40 @@ pub enum MessageContent {
     Certificate(Certificate),
     SkipCertificate(SkipCertificate),
     Gossip { data: Vec<u8> },
+    /// Rotor shred; `from_leader` marks the first hop, which the relay must forward
+    Chunk { chunk: BlockChunk, from_leader: bool },
+    /// Ask a peer to resend chunks of a block; answered with `Chunk` messages
+    RepairRequest { block_id: BlockId, chunk_ids: Vec<u32> },
+    /// Receipt for a reliable message, by its original id
+    Ack { message_id: u64 },
     Heartbeat { sequence: u64 },
     CoalitionCoordination { coalition_id: usize, instruction: CoordinationInstruction },
 }
 
+/// Framing shared by every message: sender, recipient, type and sequence number
+pub const MESSAGE_HEADER_BYTES: usize = 32;
+/// Slot, block hash, path and signature of a single vote
+pub const VOTE_WIRE_BYTES: usize = 112;
+
+impl MessageContent {
+    /// Modeled size of the message on the wire, in bytes
+    pub fn wire_size(&self) -> usize {
+        let body = match self {
+            MessageContent::Vote(_) => VOTE_WIRE_BYTES,
+            MessageContent::Certificate(cert) => 16 + cert.votes.len() * VOTE_WIRE_BYTES,
+            MessageContent::SkipCertificate(skip_cert) => 16 + skip_cert.timeout_votes.len() * VOTE_WIRE_BYTES,
+            MessageContent::Gossip { data } => data.len(),
+            // Payload plus block id, chunk id and checksum
+            MessageContent::Chunk { chunk, .. } => chunk.data.len() + 16,
+            MessageContent::RepairRequest { chunk_ids, .. } => 8 + 4 * chunk_ids.len(),
+            MessageContent::Ack { .. } => 8,
+            MessageContent::Heartbeat { .. } => 8,
+            MessageContent::CoalitionCoordination { .. } => 64,
+        };
+        MESSAGE_HEADER_BYTES + body
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum CoordinationInstruction {
     PrepareAttack { target_slot: Slot },
@@ -430,7 +751,8 @@ pub enum CoordinationInstruction {
     AbortAttack { reason: String },
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+// Ordered most urgent first
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum MessagePriority {
     Critical, // Votes, certificates
     High,     // Skip certificates, timeouts
@@ -448,10 +770,36 @@ pub enum LatencyModel {
         distance_factor: u64, // Changed to u64 for Hash
         congestion_multiplier: u64, // Changed to u64 for Hash
     },
+    /// Half the round-trip time between the validators' regions, plus per-pair jitter
+    Matrix { topology: NetworkTopology },
+}
+
+impl LatencyModel {
+    /// Latency range on `from -> to` explored by nondeterministic runs; `None` for models without randomness
+    pub fn latency_range(&self, from: NodeId, to: NodeId) -> Option<(u64, u64)> {
+        match self {
+            LatencyModel::Uniform { min_ms, max_ms } => Some((*min_ms, (*max_ms).max(*min_ms))),
+            // Two standard deviations either side of the mean
+            LatencyModel::Normal { mean_ms, std_dev_ms } => Some((
+                mean_ms.saturating_sub(2 * std_dev_ms).max(1),
+                (mean_ms + 2 * std_dev_ms).max(1),
+            )),
+            LatencyModel::Matrix { topology } => Some(topology.latency_range(from, to)),
+            LatencyModel::Constant { .. } | LatencyModel::Realistic { .. } => None,
+        }
+    }
 }
 
 pub type Bandwidth = u64; // bytes per second
 
+/// Uplink assumed for senders without an entry in `node_bandwidth`
+pub const DEFAULT_NODE_BANDWIDTH: Bandwidth = 1_000_000;
+
+/// Network delay bound when 80% of stake is responsive
+pub const DELTA_80_MS: Timestamp = 500;
+/// Network delay bound when 60% of stake is responsive
+pub const DELTA_60_MS: Timestamp = 1000;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CongestionState {
     pub current_utilization: HashMap<(NodeId, NodeId), f64>, // 0.0 to 1.0+
@@ -468,6 +816,19 @@ pub struct NetworkFailure {
     pub severity: f64, // 0.0 to 1.0
 }
 
+impl NetworkFailure {
+    pub fn is_active(&self, now: Timestamp) -> bool {
+        self.start_time <= now && now < self.start_time + self.duration
+    }
+    
+    /// Whether the failure touches `from -> to`; no affected nodes means every link
+    pub fn affects_link(&self, from: NodeId, to: NodeId) -> bool {
+        self.affected_nodes.is_empty()
+            || self.affected_nodes.contains(&from)
+            || self.affected_nodes.contains(&to)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum FailureType {
     LinkFailure { from: NodeId, to: NodeId },
@@ -484,22 +845,47 @@ impl Default for NetworkSimulationState {
             latency_model: LatencyModel::Constant { latency_ms: 50 },
             packet_loss_rate: 0.01, // 1% packet loss
             bandwidth_limits: HashMap::new(),
+            node_bandwidth: HashMap::new(),
+            uplink_free_at: HashMap::new(),
             congestion_state: CongestionState::default(),
             failure_injections: Vec::new(),
+            randomness: NetworkRandomness::default(),
+            seed: 0,
+            link_queues: HashMap::new(),
+            reliable_delivery: None,
+            fault_schedule: FaultSchedule::default(),
+            faults_released: 0,
+            partial_synchrony: None,
         }
     }
 }
 
-impl Default for MessageQueue {
-    fn default() -> Self {
-        Self {
-            pending_messages: Vec::new(),
-            delivered_messages: Vec::new(),
-            message_counter: 0,
-        }
+impl NetworkSimulationState {
+    /// Independent PRNG stream for one kind of draw (`draw`) made for message `message_id`
+    pub fn message_rng(&self, message_id: u64, draw: u64) -> StdRng {
+        let mut key = [0u8; 32];
+        key[..8].copy_from_slice(&self.seed.to_le_bytes());
+        key[8..16].copy_from_slice(&message_id.to_le_bytes());
+        key[16..24].copy_from_slice(&draw.to_le_bytes());
+        StdRng::from_seed(key)
     }
 }
 
+/// Milliseconds to push `bytes` through a `bandwidth` bytes/s pipe, rounded up
+pub fn transmission_time(bytes: usize, bandwidth: Bandwidth) -> Timestamp {
+    (bytes as u64 * 1000).div_ceil(bandwidth.max(1))
+}
+
+/// Utilization below which a link counts as idle again
+const MIN_TRACKED_UTILIZATION: f64 = 0.01;
+
+/// Deliveries a scripted repair may take before it counts as stuck
+const REPAIR_DELIVERY_LIMIT: usize = 1_000;
+
+// PRNG draws made per message
+const LOSS_DRAW: u64 = 0;
+const LATENCY_DRAW: u64 = 1;
+
 impl Default for CongestionState {
     fn default() -> Self {
         Self {
@@ -518,7 +904,10 @@ pub enum AlpenglowAction {
     Timeout { node: NodeId, slot: Slot },
     SkipCert { slot: Slot },
     AdvanceTime { delta: Timestamp },
+    Crash { node: NodeId },
+    Recover { node: NodeId },
     NetworkPartition { nodes_a: HashSet<NodeId>, nodes_b: HashSet<NodeId> },
+    PartitionNetwork { groups: Vec<HashSet<NodeId>>, one_way: Vec<(usize, usize)> },
     HealPartition,
     // Advanced coalition actions
     FormCoalition { members: Vec<NodeId>, strategy: CoalitionAttackType },
@@ -530,6 +919,7 @@ pub enum AlpenglowAction {
     SendMessage { from: NodeId, to: NodeId, content: MessageContent, priority: MessagePriority },
     DeliverMessage { message_id: u64 },
     DropMessage { message_id: u64, reason: String },
+    DelayMessage { message_id: u64, delay_ms: Timestamp },
     InjectNetworkFailure { failure: NetworkFailure },
     RecoverFromFailure { failure_index: usize },
     UpdateLatencyModel { new_model: LatencyModel },
@@ -546,11 +936,11 @@ pub enum AlpenglowAction {
     UpdateEconomicParameters { new_reward_rate: f64, new_slashing_rate: f64 },
     
     // Rotor erasure coding actions
-    PropagateErasureBlock { node: NodeId, erasure_block: ErasureCodedBlock },
+    PropagateErasureBlock { node: NodeId, slot: Slot, erasure_block: ErasureCodedBlock },
     PropagateChunk { node: NodeId, chunk: BlockChunk, target_nodes: Vec<NodeId> },
     RequestMissingChunks { node: NodeId, block_id: BlockId, missing_chunks: Vec<u32> },
     ReconstructBlock { node: NodeId, block_id: BlockId },
-    AssignRelayNodes { block_id: BlockId, relay_assignments: Vec<RelayNode> },
+    AssignRelayNodes { leader: NodeId, slot: Slot, block_id: BlockId, relay_assignments: Vec<RelayNode> },
     
     // Leader rotation and windowing actions  
     ProposeBlock { leader: NodeId, slot: Slot, block: Block, window: WindowInfo },
@@ -617,6 +1007,7 @@ impl AlpenglowState {
             // Initialize Rotor erasure coding
             erasure_coded_blocks: HashMap::new(),
             relay_assignments: HashMap::new(),
+            relay_sampling: RelaySampling::default(),
             chunk_availability: HashMap::new(),
             // Initialize leader rotation and windowing
             current_window: WindowInfo {
@@ -631,8 +1022,13 @@ impl AlpenglowState {
                 rotation_interval: 1, // Rotate every slot
                 leader_history: vec![(1, nodes[0])],
             },
-            finalization_times: HashMap::new(),
+            slot_start_times: BTreeMap::from([(1, 0)]),
+            proposals: BTreeMap::new(),
             view: 0,
+            failure_detector: None,
+            crash_budget: 0.0,
+            vote_logs: HashMap::new(),
+            metrics: MetricsHandle::default(),
         }
     }
     
@@ -701,6 +1097,24 @@ impl AlpenglowState {
             .sum()
     }
     
+    /// Mean stake per validator
+    pub fn average_stake(&self) -> StakeAmount {
+        self.total_stake() / self.nodes.len().max(1) as StakeAmount
+    }
+    
+    /// Rough chance that `members` acting together cost a slot its fast certificate: their
+    /// stake as a share of the stake the fast path can afford to lose, capped at 1
+    pub fn disruption_probability(&self, members: &[NodeId]) -> f64 {
+        let coalition_stake: StakeAmount = members.iter()
+            .map(|member| self.stake_distribution.get(member).copied().unwrap_or(0))
+            .sum();
+        let fast_path_slack = self.total_stake() - self.fast_quorum_stake();
+        if fast_path_slack == 0 {
+            return if coalition_stake > 0 { 1.0 } else { 0.0 };
+        }
+        (coalition_stake as f64 / fast_path_slack as f64).min(1.0)
+    }
+    
     pub fn byzantine_stake(&self) -> StakeAmount {
         self.stake_distribution.iter()
             .filter(|(&node, _)| matches!(self.status[&node], NodeStatus::Byzantine(_)))
@@ -708,20 +1122,362 @@ impl AlpenglowState {
             .sum()
     }
     
+    pub fn crashed_stake(&self) -> StakeAmount {
+        self.stake_distribution.iter()
+            .filter(|(&node, _)| matches!(self.status[&node], NodeStatus::Crashed { .. }))
+            .map(|(_, stake)| stake)
+            .sum()
+    }
+    
+    /// Whether crashing `node` keeps the crashed stake within `crash_budget`
+    pub fn can_crash(&self, node: NodeId) -> bool {
+        let budget = (self.total_stake() as f64 * self.crash_budget).floor() as StakeAmount;
+        let stake = self.stake_distribution.get(&node).copied().unwrap_or(0);
+        matches!(self.status[&node], NodeStatus::Honest) && self.crashed_stake() + stake <= budget
+    }
+    
+    /// Stop `node`, keeping only what it persisted: its own votes. Everything else it had
+    /// seen and its timers are lost.
+    pub fn crash(&mut self, node: NodeId) {
+        let mut log = Vec::new();
+        for slot_votes in self.votes.get_mut(&node).into_iter().flat_map(|node_votes| node_votes.values_mut()) {
+            log.extend(slot_votes.iter().filter(|vote| vote.node == node).cloned());
+            slot_votes.clear();
+        }
+        for timeout_info in self.timeouts.get_mut(&node).into_iter().flat_map(|timeouts| timeouts.values_mut()) {
+            timeout_info.count = 0;
+        }
+        self.vote_logs.insert(node, log);
+        self.status.insert(node, NodeStatus::Crashed { since: self.global_time });
+    }
+    
+    /// Restart `node` as honest from its persisted vote log, so it cannot vote again differently
+    pub fn recover(&mut self, node: NodeId) {
+        for vote in self.vote_logs.remove(&node).unwrap_or_default() {
+            self.votes.entry(node).or_default().entry(vote.slot).or_default().push(vote);
+        }
+        self.status.insert(node, NodeStatus::Honest);
+    }
+    
+    /// Crashed stake tolerated on top of `byzantine_threshold_stake` while live honest nodes
+    /// still hold a slow quorum
+    pub fn crash_threshold_stake(&self) -> StakeAmount {
+        self.total_stake().saturating_sub(self.slow_quorum_stake() + self.byzantine_threshold_stake())
+    }
+    
+    /// Whether Byzantine and crashed stake are both within what the protocol tolerates: Byzantine
+    /// stake strictly below `byzantine_threshold_stake`, as in the paper's <20%, and crashed
+    /// stake up to `crash_threshold_stake`
+    pub fn faults_within_thresholds(&self) -> bool {
+        self.byzantine_stake() < self.byzantine_threshold_stake() && self.crashed_stake() <= self.crash_threshold_stake()
+    }
+    
+    pub fn live_honest_nodes(&self) -> Vec<NodeId> {
+        self.nodes.iter()
+            .copied()
+            .filter(|node| matches!(self.status[node], NodeStatus::Honest))
+            .collect()
+    }
+    
+    /// Whether live honest nodes alone get `slot` certified on `path`: each votes for the
+    /// slot's block unless it already has, then the slot is certified
+    pub fn honest_certification_completes(&self, slot: Slot, path: VotePath) -> bool {
+        let block = self.votable_block(slot).unwrap_or(1);
+        let mut state = self.clone();
+        for node in self.live_honest_nodes() {
+            state = state.next_state(&state, AlpenglowAction::Vote { node, slot, block, path: path.clone() }).unwrap_or(state);
+        }
+        state = state.next_state(&state, AlpenglowAction::Certify { slot, path: path.clone() }).unwrap_or(state);
+        state.certificates.get(&slot).is_some_and(|cert| cert.path == path)
+    }
+    
+    /// Whether live honest nodes alone get `slot` skipped: each votes if it can, times out
+    /// until it gives up, then a skip certificate is attempted
+    pub fn honest_skip_completes(&self, slot: Slot) -> bool {
+        let block = self.votable_block(slot).unwrap_or(1);
+        let mut state = self.clone();
+        for node in self.live_honest_nodes() {
+            state = state.next_state(&state, AlpenglowAction::Vote { node, slot, block, path: VotePath::Slow }).unwrap_or(state);
+            let threshold = state.timeouts.get(&node).and_then(|timeouts| timeouts.get(&slot)).map_or(0, |info| info.threshold);
+            for _ in 0..threshold {
+                state = state.next_state(&state, AlpenglowAction::Timeout { node, slot }).unwrap_or(state);
+            }
+        }
+        state = state.next_state(&state, AlpenglowAction::SkipCert { slot }).unwrap_or(state);
+        state.skip_certs.contains_key(&slot)
+    }
+    
+    /// Whether the current slot is certified or skipped, or live honest nodes alone can
+    /// certify it on the slow path or skip it
+    pub fn current_slot_resolvable(&self) -> bool {
+        let slot = self.current_slot;
+        self.certificates.contains_key(&slot)
+            || self.skip_certs.contains_key(&slot)
+            || self.honest_certification_completes(slot, VotePath::Slow)
+            || self.honest_skip_completes(slot)
+    }
+    
+    /// Whether the current slot is skipped, or live honest nodes alone can skip it
+    pub fn current_slot_skippable(&self) -> bool {
+        let slot = self.current_slot;
+        self.skip_certs.contains_key(&slot) || self.honest_skip_completes(slot)
+    }
+    
     pub fn is_network_partitioned(&self) -> bool {
         self.network_partition.is_some()
     }
     
+    /// Whether messages from `node1` reach `node2` across the current partition and any
+    /// active partial partitions
     pub fn can_node_communicate(&self, node1: NodeId, node2: NodeId) -> bool {
-        match &self.network_partition {
-            None => true,
-            Some(partition) => {
-                (partition.partition_a.contains(&node1) && partition.partition_a.contains(&node2)) ||
-                (partition.partition_b.contains(&node1) && partition.partition_b.contains(&node2))
+        if self.network_partition.as_ref().is_some_and(|partition| !partition.allows(node1, node2)) {
+            return false;
+        }
+        !self.network_state.failure_injections.iter()
+            .filter(|failure| failure.is_active(self.global_time))
+            .any(|failure| match &failure.failure_type {
+                FailureType::PartialPartition { partition_a, partition_b } => {
+                    (partition_a.contains(&node1) && partition_b.contains(&node2))
+                        || (partition_b.contains(&node1) && partition_a.contains(&node2))
+                }
+                _ => false,
+            })
+    }
+    
+    /// Split nodes into groups holding roughly `fractions` of the total stake, largest stake first
+    pub fn stake_split(&self, fractions: &[f64]) -> Vec<HashSet<NodeId>> {
+        let total = self.total_stake() as f64;
+        let mut groups = vec![HashSet::new(); fractions.len()];
+        let mut group_stake = vec![0.0; fractions.len()];
+        
+        let mut by_stake: Vec<NodeId> = self.nodes.clone();
+        by_stake.sort_by_key(|node| (std::cmp::Reverse(self.stake_distribution.get(node).copied().unwrap_or(0)), *node));
+        for node in by_stake {
+            // Place each node in the group furthest below its target
+            let Some(group) = (0..fractions.len()).max_by(|&a, &b| {
+                let deficit = |g: usize| fractions[g] * total - group_stake[g];
+                deficit(a).total_cmp(&deficit(b)).then(b.cmp(&a))
+            }) else {
+                break;
+            };
+            groups[group].insert(node);
+            group_stake[group] += self.stake_distribution.get(&node).copied().unwrap_or(0) as f64;
+        }
+        groups
+    }
+    
+    /// Partitions worth exploring: the current leader cut off (fully, and only inbound),
+    /// and 60/40 and 20/20/60 stake splits
+    pub fn partition_scenarios(&self) -> Vec<NetworkPartition> {
+        let leader = self.get_leader_for_slot(self.current_slot);
+        let rest: HashSet<NodeId> = self.nodes.iter().copied().filter(|&node| node != leader).collect();
+        let leader_isolated = NetworkPartition::new(vec![HashSet::from([leader]), rest], self.global_time);
+        
+        vec![
+            leader_isolated.clone(),
+            // The leader still reaches everyone but hears nothing back
+            NetworkPartition { one_way: vec![(0, 1)], ..leader_isolated },
+            NetworkPartition::new(self.stake_split(&[0.6, 0.4]), self.global_time),
+            NetworkPartition::new(self.stake_split(&[0.2, 0.2, 0.6]), self.global_time),
+        ]
+    }
+    
+    /// Capacity of `from -> to` after active bandwidth reductions; `None` if the link is unthrottled
+    pub fn link_bandwidth(&self, from: NodeId, to: NodeId) -> Option<Bandwidth> {
+        let limit = self.network_state.bandwidth_limits.get(&(from, to)).copied();
+        let factor: f64 = self.network_state.failure_injections.iter()
+            .filter(|failure| failure.is_active(self.global_time) && failure.affects_link(from, to))
+            .filter_map(|failure| match failure.failure_type {
+                FailureType::BandwidthReduction { factor } => Some(factor.clamp(0.0, 1.0)),
+                _ => None,
+            })
+            .product();
+        if factor < 1.0 {
+            // Reductions apply to the link limit, or to a default uplink where none is set
+            let base = limit.unwrap_or(DEFAULT_NODE_BANDWIDTH);
+            Some(((base as f64 * factor) as Bandwidth).max(1))
+        } else {
+            limit
+        }
+    }
+    
+    /// Send nothing more on `node`'s uplink until `delay` ms from now
+    pub fn hold_uplink(&mut self, node: NodeId, delay: Timestamp) {
+        let resume = self.global_time + delay;
+        let free_at = self.network_state.uplink_free_at.entry(node).or_insert(resume);
+        *free_at = (*free_at).max(resume);
+    }
+    
+    /// Capacity of `from -> to`: its link limit, else the sender's uplink
+    pub fn link_capacity(&self, from: NodeId, to: NodeId) -> Bandwidth {
+        self.link_bandwidth(from, to).unwrap_or_else(|| {
+            self.network_state.node_bandwidth.get(&from).copied().unwrap_or(DEFAULT_NODE_BANDWIDTH)
+        })
+    }
+    
+    /// Wire bytes sent on `from -> to` and not yet delivered
+    pub fn bytes_in_flight(&self, from: NodeId, to: NodeId) -> usize {
+        self.message_queue.pending_messages.iter()
+            .filter(|msg| msg.from == from && msg.to == to)
+            .map(|msg| msg.content.wire_size())
+            .sum()
+    }
+    
+    /// Bytes in flight on `from -> to` as a fraction of what the link carries per second
+    pub fn traffic_utilization(&self, from: NodeId, to: NodeId) -> f64 {
+        self.bytes_in_flight(from, to) as f64 / self.link_capacity(from, to).max(1) as f64
+    }
+    
+    /// Raise the utilization of `from -> to` to at least its current traffic
+    fn update_link_utilization(&mut self, from: NodeId, to: NodeId) {
+        let traffic = self.traffic_utilization(from, to);
+        let utilization = self.network_state.congestion_state.current_utilization.entry((from, to)).or_default();
+        *utilization = utilization.max(traffic);
+    }
+    
+    /// Let congestion clear by `recovery_rate` per elapsed tick, but never below the traffic
+    /// still in flight; links that have cleared are forgotten
+    fn decay_congestion(&mut self, ticks: Timestamp) {
+        let retained = (1.0 - self.network_state.congestion_state.recovery_rate).clamp(0.0, 1.0).powf(ticks as f64);
+        let links: Vec<(NodeId, NodeId)> = self.network_state.congestion_state.current_utilization.keys().copied().collect();
+        for (from, to) in links {
+            let traffic = self.traffic_utilization(from, to);
+            let congestion = &mut self.network_state.congestion_state.current_utilization;
+            let utilization = (congestion[&(from, to)] * retained).max(traffic);
+            if utilization < MIN_TRACKED_UTILIZATION {
+                congestion.remove(&(from, to));
+            } else {
+                congestion.insert((from, to), utilization);
             }
         }
     }
     
+    /// Re-plan a throttled link: transmissions under way keep their slot, waiting ones go out
+    /// by priority and then send order, and pending deliveries shift with their new finish
+    fn schedule_link(&mut self, from: NodeId, to: NodeId) {
+        let now = self.global_time;
+        let Some(queue) = self.network_state.link_queues.get_mut(&(from, to)) else {
+            return;
+        };
+        
+        let (mut planned, mut waiting): (Vec<_>, Vec<_>) = std::mem::take(queue)
+            .into_iter()
+            .filter(|t| t.start.is_none_or(|start| start > now) || t.finish > now)
+            .partition(|t| t.start.is_some_and(|start| start <= now));
+        let mut clock = planned.iter().map(|t| t.finish).max().unwrap_or(now).max(now);
+        
+        while !waiting.is_empty() {
+            let earliest_ready = waiting.iter().map(|t| t.ready).min().unwrap_or(clock);
+            clock = clock.max(earliest_ready);
+            let next = waiting.iter()
+                .enumerate()
+                .filter(|(_, t)| t.ready <= clock)
+                .min_by_key(|(_, t)| (t.priority.clone(), t.message_id))
+                .map(|(index, _)| index)
+                .unwrap_or(0);
+            let mut transmission = waiting.remove(next);
+            let finish = clock + transmission.duration;
+            if let Some(msg) = self.message_queue.pending_messages.iter_mut().find(|msg| msg.id == transmission.message_id) {
+                msg.scheduled_delivery_time = (msg.scheduled_delivery_time + finish).saturating_sub(transmission.finish);
+                if let Some(deadline) = msg.deliver_by {
+                    msg.scheduled_delivery_time = msg.scheduled_delivery_time.min(deadline);
+                }
+            }
+            transmission.start = Some(clock);
+            transmission.finish = finish;
+            clock = finish;
+            planned.push(transmission);
+        }
+        
+        *queue = planned;
+    }
+    
+    /// Tag an enqueued copy of reliable message `original_id` so its receiver acknowledges it
+    fn mark_reliable(&mut self, message_id: u64, original_id: u64, retry_count: u32) {
+        if let Some(msg) = self.message_queue.pending_messages.iter_mut().find(|msg| msg.id == message_id) {
+            msg.reliable_id = Some(original_id);
+            msg.retry_count = retry_count;
+        }
+    }
+    
+    /// Replace the fault schedule and apply whatever is already due
+    pub fn load_fault_schedule(&mut self, mut schedule: FaultSchedule) {
+        schedule.events.sort_by_key(|failure| failure.start_time);
+        self.network_state.fault_schedule = schedule;
+        self.network_state.faults_released = 0;
+        self.apply_fault_schedule();
+    }
+    
+    /// Inject scheduled failures that have started and drop every failure that has run its course
+    pub fn apply_fault_schedule(&mut self) {
+        let now = self.global_time;
+        let network = &mut self.network_state;
+        while let Some(failure) = network.fault_schedule.events.get(network.faults_released) {
+            if failure.start_time > now {
+                break;
+            }
+            network.failure_injections.push(failure.clone());
+            network.faults_released += 1;
+        }
+        network.failure_injections.retain(|failure| now < failure.start_time + failure.duration);
+    }
+    
+    /// Whether an honest message has reached its partial-synchrony deadline undelivered
+    pub fn has_overdue_messages(&self) -> bool {
+        self.message_queue.pending_messages.iter()
+            .any(|msg| msg.deliver_by.is_some_and(|deadline| deadline <= self.global_time))
+    }
+    
+    /// Slots neither finalized nor skipped within `slot_bound` of max(slot start, GST)
+    pub fn post_gst_overdue_slots(&self) -> Vec<Slot> {
+        let Some(sync) = &self.network_state.partial_synchrony else {
+            return Vec::new();
+        };
+        self.slot_start_times.iter()
+            .filter(|&(slot, &start)| {
+                start.max(sync.gst) + sync.slot_bound < self.global_time
+                    && !self.certificates.contains_key(slot)
+                    && !self.skip_certs.contains_key(slot)
+            })
+            .map(|(&slot, _)| slot)
+            .collect()
+    }
+    
+    /// Whether `slot` already has a notarization or skip certificate
+    pub fn slot_resolved(&self, slot: Slot) -> bool {
+        self.certificates.contains_key(&slot) || self.skip_certs.contains_key(&slot)
+    }
+    
+    /// Report the faults in effect when `slot` first resolved, and settle what attacks achieved
+    /// there; callers check `slot_resolved` before adding the certificate
+    pub fn record_slot_outcome(&mut self, slot: Slot, outcome: SlotOutcome) {
+        self.record_attack_outcome(slot, &outcome);
+        let active_failures = self.network_state.failure_injections.iter()
+            .filter(|failure| failure.is_active(self.global_time))
+            .map(|failure| failure.failure_type.clone())
+            .collect();
+        self.metrics.record_slot_outcome(&SlotFaultReport {
+            slot,
+            outcome,
+            resolved_at: self.global_time,
+            active_failures,
+            partition: self.network_partition.clone(),
+        });
+    }
+    
+    /// Loss probability on every link right now: the baseline `packet_loss_rate` combined
+    /// with the packet-loss failures that are active
+    pub fn packet_loss_rate(&self) -> f64 {
+        let delivered: f64 = self.network_state.failure_injections.iter()
+            .filter(|failure| failure.is_active(self.global_time))
+            .filter_map(|failure| match failure.failure_type {
+                FailureType::PacketLoss { loss_rate } => Some(1.0 - loss_rate.clamp(0.0, 1.0)),
+                _ => None,
+            })
+            .product();
+        1.0 - delivered * (1.0 - self.network_state.packet_loss_rate.clamp(0.0, 1.0))
+    }
+    
     // Economic incentive methods
     pub fn calculate_epoch_rewards(&self, epoch: u64, participating_nodes: &[NodeId]) -> RewardDistribution {
         let total_available = self.economic_state.rewards_pool;
@@ -759,6 +1515,12 @@ impl AlpenglowState {
         }
     }
     
+    /// What `node` stands to lose if slashed at the current slashing rate
+    pub fn expected_slashing_loss(&self, node: NodeId) -> SlashingAmount {
+        let balance = *self.economic_state.validator_balances.get(&node).unwrap_or(&0);
+        (balance as f64 * self.economic_state.slashing_rate) as SlashingAmount
+    }
+    
     pub fn apply_slashing(&mut self, evidence: &SlashingEvidence) -> Result<SlashingAmount, String> {
         let violator = evidence.violator;
         
@@ -893,70 +1655,382 @@ impl AlpenglowState {
         }
     }
     
-    pub fn select_relay_nodes(&self, block_id: BlockId, erasure_block: &ErasureCodedBlock) -> Vec<RelayNode> {
-        let mut relay_nodes: Vec<RelayNode> = Vec::new();
-        let total_stake: StakeAmount = self.stake_distribution.values().sum();
-        
-        // Assign chunks to nodes based on stake weighting
-        for (i, chunk) in erasure_block.chunks.iter().enumerate() {
-            if let Some(node_id) = self.select_relay_node_for_chunk(chunk.chunk_id, total_stake) {
-                if let Some(existing_relay) = relay_nodes.iter_mut().find(|r| r.node_id == node_id) {
-                    existing_relay.assigned_chunks.push(chunk.chunk_id);
-                } else {
-                    let stake_weight = *self.stake_distribution.get(&node_id).unwrap_or(&0);
-                    relay_nodes.push(RelayNode {
-                        node_id,
-                        stake_weight,
-                        reliability_score: 0.95, // High reliability by default
-                        assigned_chunks: vec![chunk.chunk_id],
-                    });
-                }
-            }
+    pub fn select_relay_nodes(&self, slot: Slot, leader: NodeId, erasure_block: &ErasureCodedBlock) -> Vec<RelayNode> {
+        let mut relay_nodes: Vec<RelayNode> = Vec::new();
+        let sampled = self.sample_relays(slot, leader, erasure_block.chunks.len());
+        
+        // Assign chunks to nodes based on stake weighting
+        for (chunk, node_id) in erasure_block.chunks.iter().zip(sampled) {
+            if let Some(existing_relay) = relay_nodes.iter_mut().find(|r| r.node_id == node_id) {
+                existing_relay.assigned_chunks.push(chunk.chunk_id);
+            } else {
+                let stake_weight = *self.stake_distribution.get(&node_id).unwrap_or(&0);
+                relay_nodes.push(RelayNode {
+                    node_id,
+                    stake_weight,
+                    reliability_score: 0.95, // High reliability by default
+                    assigned_chunks: vec![chunk.chunk_id],
+                });
+            }
+        }
+        
+        relay_nodes
+    }
+    
+    /// Seed for Rotor relay sampling; identical for every run of the same (slot, leader)
+    pub fn relay_sampling_seed(slot: Slot, leader: NodeId) -> u64 {
+        ((slot as u64) << 32) | leader as u64
+    }
+    
+    /// Draw `count` relays, one per chunk, stake-weighted and deterministic in (slot, leader)
+    pub fn sample_relays(&self, slot: Slot, leader: NodeId, count: usize) -> Vec<NodeId> {
+        let candidates = self.relay_candidates();
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+        
+        let mut rng = StdRng::seed_from_u64(Self::relay_sampling_seed(slot, leader));
+        match self.relay_sampling {
+            RelaySampling::WithReplacement => {
+                (0..count).map(|_| Self::weighted_draw(&mut rng, &candidates)).collect()
+            }
+            RelaySampling::WithoutReplacement => {
+                let mut remaining = Vec::new();
+                (0..count).map(|_| {
+                    if remaining.is_empty() {
+                        remaining = candidates.clone();
+                    }
+                    let node = Self::weighted_draw(&mut rng, &remaining);
+                    remaining.retain(|&(n, _)| n != node);
+                    node
+                }).collect()
+            }
+            RelaySampling::PartitionSampling => Self::partition_sample(&mut rng, &candidates, count),
+        }
+    }
+    
+    /// Staked nodes sorted by id, so draws do not depend on HashMap iteration order
+    pub(crate) fn relay_candidates(&self) -> Vec<(NodeId, StakeAmount)> {
+        let mut candidates: Vec<(NodeId, StakeAmount)> = self.stake_distribution.iter()
+            .filter(|(_, &stake)| stake > 0)
+            .map(|(&node, &stake)| (node, stake))
+            .collect();
+        candidates.sort();
+        candidates
+    }
+    
+    fn weighted_draw(rng: &mut StdRng, candidates: &[(NodeId, StakeAmount)]) -> NodeId {
+        let total: StakeAmount = candidates.iter().map(|(_, stake)| stake).sum();
+        let mut target = rng.gen_range(0..total);
+        for &(node, stake) in candidates {
+            if target < stake {
+                return node;
+            }
+            target -= stake;
+        }
+        candidates[candidates.len() - 1].0
+    }
+    
+    fn partition_sample(rng: &mut StdRng, candidates: &[(NodeId, StakeAmount)], count: usize) -> Vec<NodeId> {
+        let (mut selection, bins) = Self::partition_bins(candidates, count);
+        
+        // Draw one node per bin proportional to its share of it
+        for bin in bins {
+            let filled: u128 = bin.iter().map(|&(_, amount)| amount).sum();
+            let mut target = rng.gen_range(0..filled);
+            for &(node, amount) in &bin {
+                if target < amount {
+                    selection.push(node);
+                    break;
+                }
+                target -= amount;
+            }
+        }
+        
+        // Shuffle so fixed slots are not always the lowest chunk ids
+        selection.shuffle(rng);
+        selection
+    }
+    
+    /// Partition sampling layout: the relay slots every node gets outright, plus one
+    /// bin of residual stake (in units of 1/total_stake of a slot) per remaining slot
+    pub(crate) fn partition_bins(
+        candidates: &[(NodeId, StakeAmount)],
+        count: usize,
+    ) -> (Vec<NodeId>, Vec<Vec<(NodeId, u128)>>) {
+        // Work in units of total_stake per slot so every share is an integer
+        let total: u128 = candidates.iter().map(|&(_, stake)| stake as u128).sum();
+        let mut fixed = Vec::with_capacity(count);
+        let mut residuals = Vec::new();
+        for &(node, stake) in candidates {
+            let scaled = stake as u128 * count as u128;
+            for _ in 0..(scaled / total) {
+                fixed.push(node);
+            }
+            let residual = scaled % total;
+            if residual > 0 {
+                residuals.push((node, residual));
+            }
+        }
+        
+        // Residuals sum to exactly one bin of size `total` per remaining slot; fill bins in order
+        let mut bins = Vec::new();
+        let mut residual_iter = residuals.into_iter();
+        let mut carry: Option<(NodeId, u128)> = None;
+        while fixed.len() + bins.len() < count {
+            let mut bin: Vec<(NodeId, u128)> = Vec::new();
+            let mut room = total;
+            while room > 0 {
+                let Some((node, amount)) = carry.take().or_else(|| residual_iter.next()) else { break };
+                let taken = amount.min(room);
+                bin.push((node, taken));
+                room -= taken;
+                if amount > taken {
+                    carry = Some((node, amount - taken));
+                }
+            }
+            if bin.is_empty() {
+                break;
+            }
+            bins.push(bin);
+        }
+        (fixed, bins)
+    }
+    
+    /// Distinct chunk ids of `block_id` that `node` has received
+    pub fn chunks_held_by(&self, node: NodeId, block_id: BlockId) -> HashSet<u32> {
+        self.chunk_availability
+            .iter()
+            .filter(|((bid, _), holders)| *bid == block_id && holders.contains(&node))
+            .map(|((_, chunk_id), _)| *chunk_id)
+            .collect()
+    }
+    
+    pub fn can_reconstruct_block(&self, node: NodeId, block_id: BlockId) -> bool {
+        if let Some(erasure_block) = self.erasure_coded_blocks.get(&block_id) {
+            // Reconstruction only uses chunks this node has actually received
+            self.chunks_held_by(node, block_id).len() >= erasure_block.required_chunks
+        } else {
+            false
+        }
+    }
+    
+    /// Record `leader`'s relay roles for `block_id`, leaving other blocks' roles intact
+    pub fn assign_relays(&mut self, slot: Slot, leader: NodeId, block_id: BlockId, relays: Vec<RelayNode>) {
+        for relay in relays {
+            self.relay_assignments.insert((block_id, relay.node_id), RelayAssignment {
+                slot,
+                block_id,
+                leader,
+                relay,
+            });
+        }
+    }
+    
+    /// Drop relay roles for a slot once it has been finalized or skipped
+    pub fn expire_relay_assignments(&mut self, slot: Slot) {
+        self.relay_assignments.retain(|_, assignment| assignment.slot != slot);
+    }
+    
+    /// Chunk ids of `block_id` that `node` has not received yet
+    pub fn missing_chunks(&self, node: NodeId, block_id: BlockId) -> Vec<u32> {
+        let held = self.chunks_held_by(node, block_id);
+        let mut missing: Vec<u32> = self.erasure_coded_blocks.get(&block_id)
+            .map(|erasure_block| erasure_block.chunks.iter()
+                .map(|chunk| chunk.chunk_id)
+                .filter(|chunk_id| !held.contains(chunk_id))
+                .collect())
+            .unwrap_or_default();
+        missing.sort();
+        missing
+    }
+    
+    /// Rotor blocks with a notarization certificate that `node` cannot reconstruct yet
+    pub fn unrepaired_notarized_blocks(&self, node: NodeId) -> Vec<BlockId> {
+        let mut blocks: Vec<BlockId> = self.certificates.values()
+            .map(|cert| cert.block)
+            .filter(|block_id| self.erasure_coded_blocks.contains_key(block_id))
+            .filter(|&block_id| !self.can_reconstruct_block(node, block_id))
+            .collect();
+        blocks.sort();
+        blocks.dedup();
+        blocks
+    }
+    
+    /// Whether every message now sent gets through: no partition, dropping failure or packet
+    /// loss is active, or partial synchrony is past GST
+    pub fn network_delivers_everything(&self) -> bool {
+        if self.network_state.partial_synchrony.as_ref().is_some_and(|sync| self.global_time >= sync.gst) {
+            return true;
+        }
+        self.network_partition.is_none()
+            && self.packet_loss_rate() == 0.0
+            && !self.network_state.failure_injections.iter()
+                .filter(|failure| failure.is_active(self.global_time))
+                .any(|failure| matches!(failure.failure_type,
+                    FailureType::LinkFailure { .. } | FailureType::NodeIsolation { .. } | FailureType::PartialPartition { .. }))
+    }
+    
+    /// Whether `node` reconstructs `block_id` if it requests its missing chunks now and the
+    /// network then delivers everything in flight
+    pub fn repair_completes(&self, node: NodeId, block_id: BlockId) -> bool {
+        let request = AlpenglowAction::RequestMissingChunks { node, block_id, missing_chunks: vec![] };
+        let mut state = self.next_state(self, request).unwrap_or_else(|| self.clone());
+        for _ in 0..REPAIR_DELIVERY_LIMIT {
+            if state.can_reconstruct_block(node, block_id) {
+                return true;
+            }
+            let Some(next) = state.message_queue.pending_messages.iter()
+                .min_by_key(|msg| (msg.scheduled_delivery_time, msg.id)) else {
+                break;
+            };
+            let (message_id, due) = (next.id, next.scheduled_delivery_time);
+            if due > state.global_time {
+                let delta = due - state.global_time;
+                state = state.next_state(&state, AlpenglowAction::AdvanceTime { delta }).unwrap_or(state);
+            }
+            state = state.next_state(&state, AlpenglowAction::DeliverMessage { message_id }).unwrap_or(state);
+        }
+        state.can_reconstruct_block(node, block_id)
+    }
+    
+    /// Note that `node` received a chunk of `block_id`, recording when it could first reconstruct
+    pub fn record_chunk_receipt(&mut self, node: NodeId, block_id: BlockId, chunk_id: u32) {
+        let reconstructed = self.can_reconstruct_block(node, block_id);
+        self.chunk_availability.entry((block_id, chunk_id)).or_default().insert(node);
+        if !reconstructed && self.can_reconstruct_block(node, block_id) {
+            self.metrics.record_block_arrival(block_id, node, self.global_time);
+        }
+    }
+    
+    /// Whether `chunk` matches what the leader encoded, standing in for Merkle proof verification
+    pub fn is_authentic_chunk(&self, chunk: &BlockChunk) -> bool {
+        self.erasure_coded_blocks.get(&chunk.block_id)
+            .and_then(|erasure_block| erasure_block.chunks.iter().find(|c| c.chunk_id == chunk.chunk_id))
+            .is_some_and(|original| original == chunk)
+    }
+    
+    /// The chunk-forwarding misbehavior of `node`, if it is a Byzantine relay
+    pub fn relay_attack(&self, node: NodeId) -> Option<RelayAttackType> {
+        match self.status.get(&node) {
+            Some(NodeStatus::Byzantine(ByzantineStrategy::RelayAttack { behavior })) => Some(behavior.clone()),
+            _ => None,
+        }
+    }
+    
+    /// Tally relay misbehavior network-wide and for every coalition `relay` belongs to
+    pub fn record_relay_attack(&mut self, relay: NodeId, withheld: u32, delayed: u32, corrupted: u32) {
+        let damage = AttackMetrics {
+            chunks_withheld: withheld,
+            chunks_delayed: delayed,
+            chunks_corrupted: corrupted,
+            ..Default::default()
+        };
+        self.metrics.record_attack(&damage);
+        let coalitions: Vec<usize> = self.byzantine_coalitions.iter()
+            .enumerate()
+            .filter(|(_, coalition)| coalition.members.contains(&relay))
+            .map(|(index, _)| index)
+            .collect();
+        for (_, coalition_state) in self.coalition_state.iter_mut().filter(|(index, _)| coalitions.contains(index)) {
+            coalition_state.success_metrics.accumulate(&damage);
+        }
+    }
+    
+    /// The proposal misbehavior of `node`, if it is a Byzantine leader
+    pub fn leader_attack(&self, node: NodeId) -> Option<LeaderAttackType> {
+        match self.status.get(&node) {
+            Some(NodeStatus::Byzantine(ByzantineStrategy::LeaderAttack { behavior })) => Some(behavior.clone()),
+            _ => None,
         }
-        
-        relay_nodes
     }
     
-    fn select_relay_node_for_chunk(&self, chunk_id: u32, total_stake: StakeAmount) -> Option<NodeId> {
-        // Stake-weighted selection with deterministic but distributed assignment
-        let seed = chunk_id as u64 * 12345; // Deterministic seed based on chunk
-        let target = seed % total_stake;
-        
-        let mut current_weight = 0;
-        for (&node_id, &stake) in &self.stake_distribution {
-            current_weight += stake;
-            if current_weight >= target {
-                return Some(node_id);
+    /// Genesis, or a block that has been notarized or finalized
+    pub fn parent_ready(&self, parent: BlockId) -> bool {
+        parent == 0
+            || self.certificates.values().any(|cert| cert.block == parent)
+            || self.ledger.iter().any(|finalized| finalized.block_id == parent)
+    }
+    
+    /// A parent id that is not ready, for a leader building on one deliberately
+    pub fn unready_parent(&self, block: &Block) -> BlockId {
+        let mut parent = block.id + 1;
+        while self.parent_ready(parent) {
+            parent += 1;
+        }
+        parent
+    }
+    
+    /// Note that `leader` proposed `block` for `slot`; a second distinct block marks equivocation
+    pub fn record_proposal(&mut self, slot: Slot, leader: NodeId, block: Option<Block>, arrives_at: Timestamp) {
+        let proposal = self.proposals.entry(slot).or_insert(Proposal {
+            leader,
+            blocks: Vec::new(),
+            arrives_at,
+        });
+        if let Some(block) = block {
+            if !proposal.blocks.iter().any(|proposed| proposed.id == block.id) {
+                proposal.blocks.push(block);
             }
         }
-        
-        self.nodes.first().copied() // Fallback
     }
     
-    pub fn can_reconstruct_block(&self, block_id: BlockId) -> bool {
-        if let Some(erasure_block) = self.erasure_coded_blocks.get(&block_id) {
-            let available_chunks: HashSet<u32> = self.chunk_availability
-                .iter()
-                .filter(|((bid, _), _)| *bid == block_id)
-                .map(|((_, chunk_id), _)| *chunk_id)
-                .collect();
-            
-            available_chunks.len() >= erasure_block.required_chunks
-        } else {
-            false
+    /// Whether `slot`'s leader gave provable cause to skip: two blocks, or one on an unready parent
+    pub fn has_faulty_proposal(&self, slot: Slot) -> bool {
+        self.proposals.get(&slot).is_some_and(|proposal| match proposal.blocks.as_slice() {
+            [] => false, // a missing proposal only shows through timeouts
+            [block] => !self.parent_ready(block.parent),
+            _ => true,
+        })
+    }
+    
+    /// The block honest nodes may vote for in a slot with a recorded proposal: the leader's
+    /// only block, once it has arrived and its parent is ready
+    pub fn votable_block(&self, slot: Slot) -> Option<BlockId> {
+        let proposal = self.proposals.get(&slot)?;
+        match proposal.blocks.as_slice() {
+            [block] if proposal.arrives_at <= self.global_time && self.parent_ready(block.parent) => Some(block.id),
+            _ => None,
         }
     }
     
-    pub fn propagate_chunks(&mut self, node_id: NodeId, erasure_block: &ErasureCodedBlock) {
-        // Update chunk availability based on relay assignments
-        if let Some(relay) = self.relay_assignments.get(&node_id) {
-            for &chunk_id in &relay.assigned_chunks {
-                self.chunk_availability
-                    .entry((erasure_block.block.id, chunk_id))
-                    .or_insert_with(HashSet::new)
-                    .insert(node_id);
-            }
+    /// Whether `slot` has a recorded proposal honest nodes cannot vote for: withheld, not yet
+    /// arrived, equivocating or built on an unready parent
+    pub fn lacks_votable_proposal(&self, slot: Slot) -> bool {
+        self.proposals.contains_key(&slot) && self.votable_block(slot).is_none()
+    }
+    
+    /// All other nodes in id order, split into `groups` contiguous groups of near-equal size
+    pub fn recipient_groups(&self, node: NodeId, groups: usize) -> Vec<Vec<NodeId>> {
+        if groups == 0 {
+            return Vec::new();
+        }
+        let mut others: Vec<NodeId> = self.nodes.iter().copied().filter(|&other| other != node).collect();
+        others.sort_unstable();
+        let mut split = vec![Vec::new(); groups];
+        let count = others.len();
+        for (i, other) in others.into_iter().enumerate() {
+            split[i * groups / count].push(other);
+        }
+        split
+    }
+    
+    /// Block `observer` could certify for `slot` from the votes it holds, with their stake
+    pub fn certifiable_in_view(&self, observer: NodeId, slot: Slot, path: VotePath) -> Option<(BlockId, StakeAmount)> {
+        let required = match path {
+            VotePath::Fast => self.fast_quorum_stake(),
+            VotePath::Slow => self.slow_quorum_stake(),
+        };
+        let held: HashSet<&Vote> = self.votes.get(&observer)?
+            .get(&slot)?
+            .iter()
+            .filter(|vote| vote.path == path)
+            .collect();
+        let mut block_stakes: BTreeMap<BlockId, StakeAmount> = BTreeMap::new();
+        for vote in held {
+            *block_stakes.entry(vote.block).or_default() += vote.stake;
         }
+        block_stakes.into_iter().find(|&(_, stake)| stake >= required)
     }
     
     // Leader rotation methods
@@ -979,7 +2053,7 @@ impl AlpenglowState {
     }
     
     pub fn update_window(&mut self, new_slot: Slot, window_size: u32, finality_depth: u32) {
-        if new_slot >= self.current_window.window_start + self.current_window.window_size as u32 {
+        if new_slot >= self.current_window.window_start + self.current_window.window_size {
             // Start new window
             self.current_window = WindowInfo {
                 window_start: new_slot,
@@ -999,8 +2073,8 @@ impl AlpenglowState {
         schedule.sort_by(|a, b| {
             let weight_a = self.stake_distribution.get(a).unwrap_or(&0);
             let weight_b = self.stake_distribution.get(b).unwrap_or(&0);
-            let hash_a = (seed.wrapping_mul(*weight_a as u64).wrapping_mul(*a as u64)) % 1000;
-            let hash_b = (seed.wrapping_mul(*weight_b as u64).wrapping_mul(*b as u64)) % 1000;
+            let hash_a = (seed.wrapping_mul(*weight_a).wrapping_mul(*a as u64)) % 1000;
+            let hash_b = (seed.wrapping_mul(*weight_b).wrapping_mul(*b as u64)) % 1000;
             hash_b.cmp(&hash_a) // Higher hash first (stake-weighted randomness)
         });
         
@@ -1008,15 +2082,13 @@ impl AlpenglowState {
     }
     
     pub fn check_finalization_time_bounds(&self, slot: Slot) -> bool {
-        if let Some(&finalization_time) = self.finalization_times.get(&slot) {
+        if let Some(finalization_time) = self.ledger.iter().find(|block| block.slot == slot).map(|block| block.finalization_time) {
             let slot_start_time = slot as Timestamp * 1000; // Assume 1 second per slot
             
             // Calculate theoretical bounds
-            let delta_80 = 500; // 500ms for 80% responsive
-            let delta_60 = 1000; // 1000ms for 60% responsive  
-            let bound = std::cmp::min(delta_80, 2 * delta_60);
+            let bound = std::cmp::min(DELTA_80_MS, 2 * DELTA_60_MS);
             
-            let actual_time = finalization_time - slot_start_time;
+            let actual_time = finalization_time.saturating_sub(slot_start_time);
             actual_time <= bound
         } else {
             true // No finalization yet, so bounds not violated
@@ -1049,6 +2121,8 @@ impl Hash for AlpenglowState {
                         ByzantineStrategy::CoalitionAttack { .. } => 5u8.hash(state),
                         ByzantineStrategy::TimingAttack { .. } => 6u8.hash(state),
                         ByzantineStrategy::StakeBasedAttack { .. } => 7u8.hash(state),
+                        ByzantineStrategy::RelayAttack { .. } => 8u8.hash(state),
+                        ByzantineStrategy::LeaderAttack { .. } => 9u8.hash(state),
                     }
                 },
                 NodeStatus::Crashed { since } => {
@@ -1062,6 +2136,7 @@ impl Hash for AlpenglowState {
         self.certificates.len().hash(state);
         self.skip_certs.len().hash(state);
         self.ledger.len().hash(state);
+        self.proposals.len().hash(state);
         
         // Hash partition status
         self.is_network_partitioned().hash(state);
@@ -1076,6 +2151,11 @@ impl Hash for AlpenglowState {
     }
 }
 
+/// The second block an equivocating leader proposes for a slot: same parent, distinct id
+pub fn conflicting_block(block: &Block) -> Block {
+    Block { id: block.id ^ 1 << 31, parent: block.parent }
+}
+
 impl Model for AlpenglowState {
     type State = AlpenglowState;
     type Action = AlpenglowAction;
@@ -1085,8 +2165,10 @@ impl Model for AlpenglowState {
     }
     
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
-        // Time advancement
-        actions.push(AlpenglowAction::AdvanceTime { delta: 1 });
+        // Time advancement, which must wait for honest messages at their synchrony deadline
+        if !state.has_overdue_messages() {
+            actions.push(AlpenglowAction::AdvanceTime { delta: 1 });
+        }
         
         // Voting actions
         for &node in &state.nodes {
@@ -1114,6 +2196,15 @@ impl Model for AlpenglowState {
             }
         }
         
+        // Crash faults within the budget, and recovery
+        for &node in &state.nodes {
+            if state.can_crash(node) {
+                actions.push(AlpenglowAction::Crash { node });
+            } else if matches!(state.status[&node], NodeStatus::Crashed { .. }) {
+                actions.push(AlpenglowAction::Recover { node });
+            }
+        }
+        
         // Certificate generation
         for slot in 1..=state.current_slot {
             actions.push(AlpenglowAction::Certify { slot, path: VotePath::Fast });
@@ -1134,15 +2225,27 @@ impl Model for AlpenglowState {
             actions.push(AlpenglowAction::SkipCert { slot });
         }
         
+        // Chunk repair for notarized blocks a node cannot reconstruct
+        for &node in &state.nodes {
+            if matches!(state.status[&node], NodeStatus::Honest) {
+                for block_id in state.unrepaired_notarized_blocks(node) {
+                    actions.push(AlpenglowAction::RequestMissingChunks {
+                        node,
+                        block_id,
+                        missing_chunks: state.missing_chunks(node, block_id),
+                    });
+                }
+            }
+        }
+        
         // Network partition scenarios
         if state.network_partition.is_none() && state.nodes.len() >= 4 {
-            let mid = state.nodes.len() / 2;
-            let partition_a: HashSet<_> = state.nodes[0..mid].iter().cloned().collect();
-            let partition_b: HashSet<_> = state.nodes[mid..].iter().cloned().collect();
-            actions.push(AlpenglowAction::NetworkPartition {
-                nodes_a: partition_a,
-                nodes_b: partition_b,
-            });
+            for partition in state.partition_scenarios() {
+                actions.push(AlpenglowAction::PartitionNetwork {
+                    groups: partition.groups,
+                    one_way: partition.one_way,
+                });
+            }
         }
         
         if state.network_partition.is_some() {
@@ -1201,7 +2304,7 @@ impl Model for AlpenglowState {
                         actions.push(AlpenglowAction::AdaptStrategy {
                             node,
                             new_strategy: ByzantineStrategy::SelectiveEquivocation {
-                                min_stake_threshold: 100,
+                                min_stake_threshold: state.average_stake(),
                                 target_slots: vec![2, 4],
                             },
                             reason: "Escalating attack".to_string(),
@@ -1233,20 +2336,41 @@ impl Model for AlpenglowState {
         match action {
             AlpenglowAction::AdvanceTime { delta } => {
                 new_state.global_time += delta;
+                new_state.decay_congestion(delta);
+                new_state.network_state.uplink_free_at.retain(|_, free_at| *free_at > new_state.global_time);
+                new_state.update_suspicions();
+                new_state.apply_fault_schedule();
+                self.retransmit_unacked(&mut new_state);
                 if new_state.global_time % 10 == 0 && new_state.current_slot < 5 {
                     new_state.current_slot += 1;
+                    new_state.slot_start_times.insert(new_state.current_slot, new_state.global_time);
+                }
+            }
+            
+            AlpenglowAction::Crash { node } => {
+                if state.can_crash(node) {
+                    new_state.crash(node);
+                }
+            }
+            
+            AlpenglowAction::Recover { node } => {
+                if matches!(state.status[&node], NodeStatus::Crashed { .. }) {
+                    new_state.recover(node);
                 }
             }
             
             AlpenglowAction::Vote { node, slot, block, path } => {
-                if matches!(state.status[&node], NodeStatus::Honest) {
+                // Where proposals are modeled, honest nodes vote only for the slot's valid block
+                let proposed = !state.proposals.contains_key(&slot) || state.votable_block(slot) == Some(block);
+                if matches!(state.status[&node], NodeStatus::Honest) && proposed {
                     let stake = *state.stake_distribution.get(&node).unwrap_or(&0);
                     let vote = Vote { node, slot, block, path, stake };
                     
                     if let Some(node_votes) = new_state.votes.get_mut(&node) {
                         if let Some(slot_votes) = node_votes.get_mut(&slot) {
-                            // Prevent double voting (honest behavior)
-                            if !slot_votes.iter().any(|v| v.block == block && v.path == vote.path) {
+                            // Prevent double voting (honest behavior), including against votes
+                            // restored from the log after a crash
+                            if !slot_votes.iter().any(|v| v.node == node && v.path == vote.path) {
                                 slot_votes.push(vote);
                             }
                         }
@@ -1273,14 +2397,14 @@ impl Model for AlpenglowState {
                     }
                 }
                 
-                // Group by block and calculate stake
-                let mut block_stakes: HashMap<BlockId, StakeAmount> = HashMap::new();
+                // Group by block and calculate stake; a vote held by several nodes counts once
                 let mut block_votes: HashMap<BlockId, HashSet<Vote>> = HashMap::new();
-                
                 for vote in all_votes {
-                    *block_stakes.entry(vote.block).or_insert(0) += vote.stake;
-                    block_votes.entry(vote.block).or_insert_with(HashSet::new).insert(vote);
+                    block_votes.entry(vote.block).or_default().insert(vote);
                 }
+                let block_stakes: HashMap<BlockId, StakeAmount> = block_votes.iter()
+                    .map(|(&block, votes)| (block, votes.iter().map(|vote| vote.stake).sum()))
+                    .collect();
                 
                 // Check if any block has enough stake for certification
                 let required_stake = match path {
@@ -1299,7 +2423,12 @@ impl Model for AlpenglowState {
                             total_stake,
                             path: path.clone(),
                         };
+                        let first = !new_state.slot_resolved(slot);
                         new_state.certificates.insert(slot, certificate);
+                        new_state.expire_relay_assignments(slot);
+                        if first {
+                            new_state.record_slot_outcome(slot, SlotOutcome::Finalized);
+                        }
                         
                         // Add to ledger
                         if !new_state.ledger.iter().any(|fb| fb.slot == slot) {
@@ -1315,9 +2444,16 @@ impl Model for AlpenglowState {
             }
             
             AlpenglowAction::Timeout { node, slot } => {
+                // A suspected leader's slot, or one with a provably bad proposal, is given up at
+                // once instead of waiting out the timeouts
+                let skip_early = state.suspects_leader(node, slot) || state.has_faulty_proposal(slot);
                 if let Some(node_timeouts) = new_state.timeouts.get_mut(&node) {
                     if let Some(timeout_info) = node_timeouts.get_mut(&slot) {
-                        timeout_info.count += 1;
+                        timeout_info.count = if skip_early {
+                            timeout_info.count.max(timeout_info.threshold)
+                        } else {
+                            timeout_info.count + 1
+                        };
                         timeout_info.last_timeout = state.global_time;
                     }
                 }
@@ -1329,7 +2465,7 @@ impl Model for AlpenglowState {
                     .filter(|&&node| {
                         state.timeouts.get(&node)
                             .and_then(|timeouts| timeouts.get(&slot))
-                            .map_or(false, |info| info.count >= info.threshold)
+                            .is_some_and(|info| info.count >= info.threshold)
                     })
                     .count();
                 
@@ -1346,21 +2482,42 @@ impl Model for AlpenglowState {
                         }
                     }
                     
+                    // When the leader gave honest nodes nothing to vote for, the nodes that
+                    // timed out back the skip with their own stake
+                    if state.lacks_votable_proposal(slot) {
+                        let timed_out_stake: StakeAmount = state.nodes.iter()
+                            .filter(|&&node| state.timeouts.get(&node)
+                                .and_then(|timeouts| timeouts.get(&slot))
+                                .is_some_and(|info| info.count >= info.threshold))
+                            .map(|node| state.stake_distribution.get(node).copied().unwrap_or(0))
+                            .sum();
+                        total_stake = total_stake.max(timed_out_stake);
+                    }
+                    
                     if total_stake >= state.slow_quorum_stake() {
                         let skip_cert = SkipCertificate {
                             slot,
                             timeout_votes,
                             total_stake,
                         };
+                        let first = !new_state.slot_resolved(slot);
                         new_state.skip_certs.insert(slot, skip_cert);
+                        new_state.expire_relay_assignments(slot);
+                        if first {
+                            new_state.record_slot_outcome(slot, SlotOutcome::Skipped);
+                        }
                     }
                 }
             }
             
             AlpenglowAction::NetworkPartition { nodes_a, nodes_b } => {
+                new_state.network_partition = Some(NetworkPartition::new(vec![nodes_a, nodes_b], state.global_time));
+            }
+            
+            AlpenglowAction::PartitionNetwork { groups, one_way } => {
                 new_state.network_partition = Some(NetworkPartition {
-                    partition_a: nodes_a,
-                    partition_b: nodes_b,
+                    groups,
+                    one_way,
                     started_at: state.global_time,
                 });
             }
@@ -1387,12 +2544,7 @@ impl Model for AlpenglowState {
                 new_state.coalition_state.insert(coalition_index, CoalitionState {
                     active: true,
                     current_phase: AttackPhase::Preparation,
-                    success_metrics: AttackMetrics {
-                        slots_disrupted: 0,
-                        certificates_prevented: 0,
-                        timeouts_caused: 0,
-                        economic_damage: 0,
-                    },
+                    success_metrics: AttackMetrics::default(),
                     adaptation_count: 0,
                 });
             }
@@ -1438,6 +2590,14 @@ impl Model for AlpenglowState {
                 new_state.message_queue.pending_messages.retain(|msg| msg.id != message_id);
             }
             
+            AlpenglowAction::DelayMessage { message_id, delay_ms } => {
+                if let Some(msg) = new_state.message_queue.pending_messages.iter_mut().find(|msg| msg.id == message_id) {
+                    let delay = delay_ms.min(msg.latency_slack);
+                    msg.scheduled_delivery_time += delay;
+                    msg.latency_slack -= delay;
+                }
+            }
+            
             AlpenglowAction::InjectNetworkFailure { failure } => {
                 new_state.network_state.failure_injections.push(failure);
             }
@@ -1514,60 +2674,62 @@ impl Model for AlpenglowState {
             }
             
             // Rotor erasure coding actions
-            AlpenglowAction::PropagateErasureBlock { node, erasure_block } => {
+            AlpenglowAction::PropagateErasureBlock { node, slot, erasure_block } => {
                 new_state.erasure_coded_blocks.insert(erasure_block.block.id, erasure_block.clone());
-                let relay_nodes = new_state.select_relay_nodes(erasure_block.block.id, &erasure_block);
-                for relay in relay_nodes {
-                    new_state.relay_assignments.insert(relay.node_id, relay);
-                }
-                new_state.propagate_chunks(node, &erasure_block);
+                let relay_nodes = new_state.select_relay_nodes(slot, node, &erasure_block);
+                new_state.assign_relays(slot, node, erasure_block.block.id, relay_nodes);
+                self.dispatch_erasure_block(&mut new_state, node, slot, &erasure_block);
             }
             
             AlpenglowAction::PropagateChunk { node, chunk, target_nodes } => {
-                // Update chunk availability for target nodes
-                for &target in &target_nodes {
-                    new_state.chunk_availability
-                        .entry((chunk.block_id, chunk.chunk_id))
-                        .or_insert_with(HashSet::new)
-                        .insert(target);
-                }
+                self.relay_chunk(&mut new_state, node, chunk, &target_nodes);
             }
             
-            AlpenglowAction::RequestMissingChunks { node, block_id, missing_chunks: _ } => {
-                // In practice, this would trigger chunk retrieval
-                // For formal verification, we just ensure the request is valid
-                if new_state.erasure_coded_blocks.contains_key(&block_id) {
-                    // Valid request - would trigger chunk propagation in real implementation
-                }
+            AlpenglowAction::RequestMissingChunks { node, block_id, missing_chunks } => {
+                self.request_repair(&mut new_state, node, block_id, &missing_chunks);
             }
             
             AlpenglowAction::ReconstructBlock { node, block_id } => {
-                if new_state.can_reconstruct_block(block_id) {
-                    // Block can be reconstructed - mark as available to node
+                if new_state.can_reconstruct_block(node, block_id) {
+                    // Decoding recovers the full block, so the node now holds every chunk
                     if let Some(erasure_block) = new_state.erasure_coded_blocks.get(&block_id) {
                         for chunk in &erasure_block.chunks {
                             new_state.chunk_availability
                                 .entry((block_id, chunk.chunk_id))
-                                .or_insert_with(HashSet::new)
+                                .or_default()
                                 .insert(node);
                         }
                     }
                 }
             }
             
-            AlpenglowAction::AssignRelayNodes { block_id, relay_assignments } => {
-                for relay in relay_assignments {
-                    new_state.relay_assignments.insert(relay.node_id, relay);
-                }
+            AlpenglowAction::AssignRelayNodes { leader, slot, block_id, relay_assignments } => {
+                new_state.assign_relays(slot, leader, block_id, relay_assignments);
             }
             
             // Leader rotation and windowing actions
-            AlpenglowAction::ProposeBlock { leader, slot, block, window } => {
+            AlpenglowAction::ProposeBlock { leader, slot, block, window: _ } => {
                 // Verify leader is authorized for this slot
                 let expected_leader = new_state.get_leader_for_slot(slot);
                 if leader == expected_leader {
-                    // Valid proposal - could add to pending blocks
                     new_state.current_slot = slot.max(new_state.current_slot);
+                    let now = new_state.global_time;
+                    match new_state.leader_attack(leader) {
+                        None => new_state.record_proposal(slot, leader, Some(block), now),
+                        Some(LeaderAttackType::WithholdProposal) => new_state.record_proposal(slot, leader, None, now),
+                        Some(LeaderAttackType::EquivocateProposal) => {
+                            let conflicting = conflicting_block(&block);
+                            new_state.record_proposal(slot, leader, Some(block), now);
+                            new_state.record_proposal(slot, leader, Some(conflicting), now);
+                        }
+                        Some(LeaderAttackType::UnreadyParent) => {
+                            let parent = new_state.unready_parent(&block);
+                            new_state.record_proposal(slot, leader, Some(Block { id: block.id, parent }), now);
+                        }
+                        Some(LeaderAttackType::LateProposal { delay_ms }) => {
+                            new_state.record_proposal(slot, leader, Some(block), now + delay_ms);
+                        }
+                    }
                 }
             }
             
@@ -1654,7 +2816,7 @@ impl Model for AlpenglowState {
                             let mut vote_groups: HashMap<(Slot, VotePath), Vec<&Vote>> = HashMap::new();
                             for vote in slot_votes {
                                 vote_groups.entry((vote.slot, vote.path.clone()))
-                                    .or_insert_with(Vec::new)
+                                    .or_default()
                                     .push(vote);
                             }
                             
@@ -1671,11 +2833,57 @@ impl Model for AlpenglowState {
                 true
             }),
             
+            // Within the Byzantine and crash thresholds, live honest nodes alone still resolve
+            // the current slot, by notarizing it or else by skipping it
+            Property::always("crash_fault_liveness", |_, state: &Self::State| {
+                !state.faults_within_thresholds() || state.current_slot_resolvable()
+            }),
+            
+            // Under the same faults, live honest nodes alone can always skip the current slot
+            Property::always("crash_fault_skip_liveness", |_, state: &Self::State| {
+                !state.faults_within_thresholds() || state.current_slot_skippable()
+            }),
+            
+            // Honest nodes skip slots whose leader equivocated, withheld, proposed late or built
+            // on an unready parent, rather than certify anything but the leader's valid block:
+            // once every live honest node has timed out on such a slot, it gets a skip certificate
+            Property::always("faulty_leader_slots_skipped", |_, state: &Self::State| {
+                if state.byzantine_stake() > (20 * state.total_stake()) / 100 {
+                    return true;
+                }
+                let certified_valid = state.certificates.iter().all(|(slot, cert)| {
+                    !state.proposals.contains_key(slot) || state.votable_block(*slot) == Some(cert.block)
+                });
+                let all_timed_out = |slot: Slot| state.live_honest_nodes().iter().all(|node| {
+                    state.timeouts.get(node)
+                        .and_then(|timeouts| timeouts.get(&slot))
+                        .is_some_and(|info| info.count >= info.threshold)
+                });
+                certified_valid && (!state.faults_within_thresholds() || state.proposals.keys()
+                    .filter(|&&slot| state.lacks_votable_proposal(slot) && !state.slot_resolved(slot) && all_timed_out(slot))
+                    .all(|&slot| state.next_state(state, AlpenglowAction::SkipCert { slot })
+                        .is_some_and(|skipped| skipped.skip_certs.contains_key(&slot))))
+            }),
+            
+            // No finalized block is one of two conflicting proposals for its slot
+            Property::always("no_conflicting_finalization", |_, state: &Self::State| {
+                if state.byzantine_stake() > (20 * state.total_stake()) / 100 {
+                    return true;
+                }
+                let mut finalized: HashMap<Slot, BlockId> = HashMap::new();
+                state.ledger.iter().all(|block| {
+                    let unique = *finalized.entry(block.slot).or_insert(block.block_id) == block.block_id;
+                    let proposed_alone = state.proposals.get(&block.slot)
+                        .is_none_or(|proposal| proposal.blocks.len() == 1 && proposal.blocks[0].id == block.block_id);
+                    unique && proposed_alone
+                })
+            }),
+            
             // Bounded finalization time: min(δ₈₀%, 2δ₆₀%)
             Property::always("bounded_finalization_time", |_, state: &Self::State| {
                 // Check all finalized slots meet time bounds
-                for &slot in state.finalization_times.keys() {
-                    if !state.check_finalization_time_bounds(slot) {
+                for block in &state.ledger {
+                    if !state.check_finalization_time_bounds(block.slot) {
                         return false;
                     }
                 }
@@ -1684,20 +2892,47 @@ impl Model for AlpenglowState {
             
             // Rotor erasure coding availability
             Property::always("erasure_block_availability", |_, state: &Self::State| {
-                // All erasure coded blocks should be reconstructible by honest nodes
-                for (&block_id, erasure_block) in &state.erasure_coded_blocks {
-                    let available_chunks: HashSet<u32> = state.chunk_availability
-                        .iter()
-                        .filter(|((bid, _), _)| *bid == block_id)
-                        .map(|((_, chunk_id), _)| *chunk_id)
+                // Every honest node that voted for a block could reconstruct it
+                for (&node, node_votes) in &state.votes {
+                    if !matches!(state.status[&node], NodeStatus::Honest) {
+                        continue;
+                    }
+                    for slot_votes in node_votes.values() {
+                        for vote in slot_votes.iter().filter(|v| v.node == node) {
+                            if state.erasure_coded_blocks.contains_key(&vote.block) &&
+                               !state.can_reconstruct_block(node, vote.block) {
+                                return false;
+                            }
+                        }
+                    }
+                }
+                true
+            }),
+            
+            // Chunk repair: a notarized Rotor block stays recoverable from honest nodes, and once
+            // the network delivers again (healed, or past GST) every live honest node that missed
+            // it during dissemination repairs it
+            Property::always("notarized_block_repairable", |_, state: &Self::State| {
+                for cert in state.certificates.values() {
+                    let Some(erasure_block) = state.erasure_coded_blocks.get(&cert.block) else {
+                        continue;
+                    };
+                    let honest_chunks: HashSet<u32> = state.nodes.iter()
+                        .filter(|&&node| matches!(state.status[&node], NodeStatus::Honest))
+                        .flat_map(|&node| state.chunks_held_by(node, cert.block))
                         .collect();
-                    
-                    // Should have enough chunks for reconstruction
-                    if available_chunks.len() < erasure_block.required_chunks {
+                    if honest_chunks.len() < erasure_block.required_chunks {
                         return false;
                     }
                 }
-                true
+                !state.network_delivers_everything() || state.live_honest_nodes().into_iter().all(|node| {
+                    state.unrepaired_notarized_blocks(node).into_iter().all(|block_id| state.repair_completes(node, block_id))
+                })
+            }),
+            
+            // Liveness under partial synchrony: after GST every slot resolves within its bound
+            Property::always("post_gst_liveness", |_, state: &Self::State| {
+                state.post_gst_overdue_slots().is_empty()
             }),
             
             // Leader rotation fairness
@@ -1736,10 +2971,10 @@ impl AlpenglowState {
     ) {
         match strategy {
             ByzantineStrategy::Equivocation => {
-                // Basic equivocation: vote for multiple blocks
-                for block in 0..2 {
-                    let vote = Vote { node, slot, block, path: VotePath::Fast, stake };
-                    self.add_vote_to_state(state, vote);
+                // Basic equivocation: one half of the network sees a vote for block 0, the other for block 1
+                for (block, recipients) in state.recipient_groups(node, 2).into_iter().enumerate() {
+                    let vote = Vote { node, slot, block: block as BlockId, path: VotePath::Fast, stake };
+                    self.send_vote_to(state, vote, &recipients);
                 }
             }
             
@@ -1800,9 +3035,13 @@ impl AlpenglowState {
                 self.add_vote_to_state(state, vote);
             }
             
-            ByzantineStrategy::StakeBasedAttack { reserve_stake_for_critical_slots, activation_threshold, min_profit_margin: _ } => {
+            ByzantineStrategy::StakeBasedAttack { reserve_stake_for_critical_slots, activation_threshold, min_profit_margin } => {
                 if stake >= *activation_threshold {
-                    if *reserve_stake_for_critical_slots && slot % 3 == 0 {
+                    if state.expected_slashing_loss(node) >= *min_profit_margin {
+                        // The attack would not pay for the slashing it risks
+                        let vote = Vote { node, slot, block: 0, path: VotePath::Fast, stake };
+                        self.add_vote_to_state(state, vote);
+                    } else if *reserve_stake_for_critical_slots && slot.is_multiple_of(3) {
                         // Critical slot: maximize disruption
                         for block in 0..3 {
                             let vote = Vote { node, slot, block, path: VotePath::Fast, stake };
@@ -1815,6 +3054,22 @@ impl AlpenglowState {
                     }
                 }
             }
+            
+            ByzantineStrategy::RelayAttack { .. } => {
+                // Misbehaves only when relaying chunks; votes like an honest node
+                let vote = Vote { node, slot, block: 0, path: VotePath::Fast, stake };
+                self.add_vote_to_state(state, vote);
+            }
+            
+            ByzantineStrategy::LeaderAttack { .. } => {
+                // Misbehaves only in its own slots; backs whatever it proposed there
+                let block = state.proposals.get(&slot)
+                    .filter(|proposal| proposal.leader == node)
+                    .and_then(|proposal| proposal.blocks.first())
+                    .map_or(0, |block| block.id);
+                let vote = Vote { node, slot, block, path: VotePath::Fast, stake };
+                self.add_vote_to_state(state, vote);
+            }
         }
     }
     
@@ -1826,6 +3081,15 @@ impl AlpenglowState {
         }
     }
     
+    /// Send `vote` to `recipients` only; the sender still records every vote it signed
+    fn send_vote_to(&self, state: &mut AlpenglowState, vote: Vote, recipients: &[NodeId]) {
+        self.add_vote_to_state(state, vote.clone());
+        for &to in recipients {
+            let content = MessageContent::Vote(vote.clone());
+            self.send_message_with_delay(state, vote.node, to, content, MessagePriority::Critical, 0);
+        }
+    }
+    
     fn execute_coalition_attack(
         &self,
         state: &mut AlpenglowState,
@@ -1841,26 +3105,34 @@ impl AlpenglowState {
         
         match attack_type {
             CoalitionAttackType::SplitVote { target_blocks } => {
-                let node_index = coalition_members.iter().position(|&n| n == node).unwrap_or(0);
-                let block = target_blocks.get(node_index % target_blocks.len()).copied().unwrap_or(0);
-                let vote = Vote { node, slot, block, path: VotePath::Fast, stake };
-                self.add_vote_to_state(state, vote);
+                // Every member shows recipient group k the same block, target_blocks[k]
+                let groups = state.recipient_groups(node, target_blocks.len());
+                for (&block, recipients) in target_blocks.iter().zip(groups) {
+                    let vote = Vote { node, slot, block, path: VotePath::Fast, stake };
+                    self.send_vote_to(state, vote, &recipients);
+                }
             }
             
             CoalitionAttackType::DelayedFlood { delay_until_slot } => {
                 if slot >= *delay_until_slot {
-                    // Flood with multiple votes
+                    // Flood with multiple votes, each broadcast to every other node
+                    let recipients: Vec<NodeId> = state.nodes.iter().copied().filter(|&to| to != node).collect();
                     for block in 0..2 {
                         for path in [VotePath::Fast, VotePath::Slow] {
                             let vote = Vote { node, slot, block, path, stake };
-                            self.add_vote_to_state(state, vote);
+                            self.add_vote_to_state(state, vote.clone());
+                            for &to in &recipients {
+                                let content = MessageContent::Vote(vote.clone());
+                                self.send_message_with_delay(state, node, to, content, MessagePriority::Critical, 0);
+                            }
                         }
                     }
                 }
             }
             
-            CoalitionAttackType::StrategicTargeting { high_priority_slots, disruption_threshold: _ } => {
-                if high_priority_slots.contains(&slot) {
+            CoalitionAttackType::StrategicTargeting { high_priority_slots, disruption_threshold } => {
+                let disruption = state.disruption_probability(coalition_members);
+                if high_priority_slots.contains(&slot) && disruption > *disruption_threshold {
                     // Maximum disruption on targeted slots
                     for block in 0..3 {
                         let vote = Vote { node, slot, block, path: VotePath::Fast, stake };
@@ -1881,16 +3153,18 @@ impl AlpenglowState {
                     }
                     
                     CertManipulationType::ConflictingCertificates => {
-                        // Vote for different blocks to create conflicts
-                        let block = (node + slot) % 3;
-                        let vote = Vote { node, slot, block, path: target_path.clone(), stake };
-                        self.add_vote_to_state(state, vote);
+                        // Back a different block in each half, so each half can certify its own
+                        for (half, recipients) in state.recipient_groups(node, 2).into_iter().enumerate() {
+                            let block = (slot + half as Slot) % 3;
+                            let vote = Vote { node, slot, block, path: target_path.clone(), stake };
+                            self.send_vote_to(state, vote, &recipients);
+                        }
                     }
                     
-                    CertManipulationType::DelayedCertification { delay_slots: _ } => {
-                        // Delay voting by a few slots
-                        if slot > 2 {
-                            let delayed_slot = slot - 2;
+                    CertManipulationType::DelayedCertification { delay_slots } => {
+                        // Vote only now for the slot `delay_slots` back
+                        if slot > *delay_slots {
+                            let delayed_slot = slot - delay_slots;
                             let vote = Vote { node, slot: delayed_slot, block: 0, path: target_path.clone(), stake };
                             self.add_vote_to_state(state, vote);
                         }
@@ -1902,15 +3176,47 @@ impl AlpenglowState {
     
     /// Network simulation helper methods
     fn generate_network_actions(&self, state: &AlpenglowState, actions: &mut Vec<AlpenglowAction>) {
-        // Message delivery actions
-        for pending_msg in &state.message_queue.pending_messages {
-            if pending_msg.scheduled_delivery_time <= state.global_time {
+        // Message delivery actions: each receiver takes its most urgent due messages first
+        let due: Vec<&PendingMessage> = state.message_queue.pending_messages.iter()
+            .filter(|msg| msg.scheduled_delivery_time <= state.global_time)
+            .collect();
+        let mut most_urgent: HashMap<NodeId, &MessagePriority> = HashMap::new();
+        for msg in &due {
+            let urgent = most_urgent.entry(msg.to).or_insert(&msg.priority);
+            if msg.priority < **urgent {
+                *urgent = &msg.priority;
+            }
+        }
+        for pending_msg in due {
+            if most_urgent[&pending_msg.to] == &pending_msg.priority {
                 actions.push(AlpenglowAction::DeliverMessage { 
                     message_id: pending_msg.id 
                 });
             }
         }
         
+        // Adversarial delay within each message's slack
+        for pending_msg in &state.message_queue.pending_messages {
+            if pending_msg.latency_slack > 0 {
+                actions.push(AlpenglowAction::DelayMessage {
+                    message_id: pending_msg.id,
+                    delay_ms: pending_msg.latency_slack,
+                });
+            }
+        }
+        
+        // Loss as an explicit branch when the checker owns randomness
+        if state.network_state.randomness == NetworkRandomness::Nondeterministic {
+            for pending_msg in &state.message_queue.pending_messages {
+                if state.packet_loss_rate() > 0.0 && pending_msg.deliver_by.is_none() {
+                    actions.push(AlpenglowAction::DropMessage {
+                        message_id: pending_msg.id,
+                        reason: "packet loss".to_string(),
+                    });
+                }
+            }
+        }
+        
         // Spontaneous message sending (gossip, heartbeats)
         for &from in &state.nodes {
             if matches!(state.status[&from], NodeStatus::Honest | NodeStatus::Byzantine(_)) {
@@ -1942,8 +3248,10 @@ impl AlpenglowState {
             }
         }
         
-        // Network failure injection
-        if state.network_state.failure_injections.is_empty() && state.global_time > 10 {
+        // Network failure injection, unless the scenario brought its own schedule
+        if state.network_state.failure_injections.is_empty()
+            && state.network_state.fault_schedule.events.is_empty()
+            && state.global_time > 10 {
             // Inject random network failures
             actions.push(AlpenglowAction::InjectNetworkFailure {
                 failure: NetworkFailure {
@@ -1966,26 +3274,8 @@ impl AlpenglowState {
             });
         }
         
-        // Congestion simulation
-        if state.global_time % 20 == 0 {
-            let mut congested_links = Vec::new();
-            for &from in &state.nodes {
-                for &to in &state.nodes {
-                    if from != to && (from + to) % 3 == 0 {
-                        congested_links.push((from, to));
-                    }
-                }
-            }
-            if !congested_links.is_empty() {
-                actions.push(AlpenglowAction::SimulateCongestion {
-                    links: congested_links,
-                    intensity: 0.9,
-                });
-            }
-        }
-        
         // Dynamic latency model updates
-        if state.global_time % 50 == 0 {
+        if state.global_time.is_multiple_of(50) {
             actions.push(AlpenglowAction::UpdateLatencyModel {
                 new_model: LatencyModel::Normal { 
                     mean_ms: 100, 
@@ -1995,6 +3285,165 @@ impl AlpenglowState {
         }
     }
     
+    /// Time for `from` to put `bytes` on the wire at its uplink bandwidth
+    pub fn serialization_delay(&self, state: &AlpenglowState, from: NodeId, bytes: usize) -> Timestamp {
+        let bandwidth = state.network_state.node_bandwidth
+            .get(&from)
+            .copied()
+            .unwrap_or(DEFAULT_NODE_BANDWIDTH);
+        transmission_time(bytes, bandwidth)
+    }
+    
+    /// Put `bytes` for `to` on `from`'s uplink behind its earlier chunks and return how long
+    /// until they are sent. A throttled link serializes in its own queue, so there the chunk
+    /// only waits for the uplink to free up.
+    fn uplink_delay(&self, state: &mut AlpenglowState, from: NodeId, to: NodeId, bytes: usize) -> Timestamp {
+        let now = state.global_time;
+        let free_at = state.network_state.uplink_free_at.get(&from).copied().unwrap_or(now).max(now);
+        if state.link_bandwidth(from, to).is_some() {
+            return free_at - now;
+        }
+        let finish = free_at + self.serialization_delay(state, from, bytes);
+        state.network_state.uplink_free_at.insert(from, finish);
+        finish - now
+    }
+    
+    /// First Rotor hop: the leader keeps every chunk and sends each relay its assigned chunks.
+    /// A Byzantine leader may send nothing, start late, or give half the relays the chunks
+    /// of a conflicting block.
+    fn dispatch_erasure_block(&self, state: &mut AlpenglowState, leader: NodeId, slot: Slot, erasure_block: &ErasureCodedBlock) {
+        let block_id = erasure_block.block.id;
+        let mut conflicting = None;
+        match state.leader_attack(leader) {
+            Some(LeaderAttackType::WithholdProposal) => return,
+            Some(LeaderAttackType::LateProposal { delay_ms }) => state.hold_uplink(leader, delay_ms),
+            Some(LeaderAttackType::EquivocateProposal) => {
+                let other = state.create_erasure_coded_block(conflicting_block(&erasure_block.block), erasure_block.redundancy_level);
+                state.record_proposal(slot, leader, Some(erasure_block.block.clone()), state.global_time);
+                state.record_proposal(slot, leader, Some(other.block.clone()), state.global_time);
+                state.erasure_coded_blocks.insert(other.block.id, other.clone());
+                for chunk in &other.chunks {
+                    state.record_chunk_receipt(leader, other.block.id, chunk.chunk_id);
+                }
+                conflicting = Some(other);
+            }
+            _ => {}
+        }
+        
+        state.metrics.record_block_dispatch(block_id, state.global_time);
+        for chunk in &erasure_block.chunks {
+            state.record_chunk_receipt(leader, block_id, chunk.chunk_id);
+        }
+        
+        let mut assignments: Vec<&RelayAssignment> = state.relay_assignments.values()
+            .filter(|assignment| assignment.block_id == block_id)
+            .collect();
+        assignments.sort_by_key(|assignment| assignment.relay.node_id);
+        
+        let half = assignments.len() / 2;
+        let mut outgoing = Vec::new();
+        let mut conflicting_relays = Vec::new();
+        for (index, assignment) in assignments.into_iter().enumerate() {
+            let source = match &conflicting {
+                Some(other) if index >= half && assignment.relay.node_id != leader => {
+                    conflicting_relays.push(assignment.relay.clone());
+                    other
+                }
+                _ => erasure_block,
+            };
+            for &chunk_id in &assignment.relay.assigned_chunks {
+                if let Some(chunk) = source.chunks.iter().find(|c| c.chunk_id == chunk_id) {
+                    outgoing.push((assignment.relay.node_id, chunk.clone()));
+                }
+            }
+        }
+        if let Some(other) = &conflicting {
+            // Relays handed the conflicting block forward it just as they would the real one
+            state.assign_relays(slot, leader, other.block.id, conflicting_relays);
+        }
+        
+        // The leader's uplink sends chunks back to back
+        for (relay, chunk) in outgoing {
+            if relay == leader {
+                // The leader relays its own chunks straight to everyone
+                let targets = state.nodes.clone();
+                self.relay_chunk(state, leader, chunk, &targets);
+                continue;
+            }
+            let content = MessageContent::Chunk { chunk, from_leader: true };
+            let delay = self.uplink_delay(state, leader, relay, content.wire_size());
+            self.send_message_with_delay(state, leader, relay, content, MessagePriority::High, delay);
+        }
+    }
+    
+    /// Second Rotor hop: a holder of `chunk` forwards it to `targets`, queued on its uplink
+    /// behind every chunk it is already sending
+    fn relay_chunk(&self, state: &mut AlpenglowState, relay: NodeId, chunk: BlockChunk, targets: &[NodeId]) {
+        let holds_chunk = state.chunk_availability
+            .get(&(chunk.block_id, chunk.chunk_id))
+            .is_some_and(|holders| holders.contains(&relay));
+        if !holds_chunk {
+            return;
+        }
+        
+        let recipients: Vec<NodeId> = targets.iter().copied().filter(|&target| target != relay).collect();
+        let mut sent_to = recipients.clone();
+        let mut chunk = chunk;
+        let (mut delayed, mut corrupted) = (0, 0);
+        match state.relay_attack(relay) {
+            None => {}
+            Some(RelayAttackType::DropChunks) => sent_to.clear(),
+            Some(RelayAttackType::ForwardToSubset { recipients: allowed }) => {
+                sent_to.retain(|target| allowed.contains(target));
+            }
+            Some(RelayAttackType::DelayForwarding { delay_ms }) => {
+                state.hold_uplink(relay, delay_ms);
+                delayed = sent_to.len() as u32;
+            }
+            Some(RelayAttackType::CorruptChunks) => {
+                for byte in chunk.data.iter_mut() {
+                    *byte ^= 0xFF;
+                }
+                corrupted = sent_to.len() as u32;
+            }
+        }
+        let withheld = (recipients.len() - sent_to.len()) as u32;
+        if withheld + delayed + corrupted > 0 {
+            state.record_relay_attack(relay, withheld, delayed, corrupted);
+        }
+        
+        for target in sent_to {
+            let content = MessageContent::Chunk { chunk: chunk.clone(), from_leader: false };
+            let delay = self.uplink_delay(state, relay, target, content.wire_size());
+            self.send_message_with_delay(state, relay, target, content, MessagePriority::High, delay);
+        }
+    }
+    
+    /// Ask peers holding the requested chunks to resend them; an empty list requests every missing chunk
+    fn request_repair(&self, state: &mut AlpenglowState, node: NodeId, block_id: BlockId, requested: &[u32]) {
+        let missing = state.missing_chunks(node, block_id);
+        let wanted: Vec<u32> = if requested.is_empty() {
+            missing
+        } else {
+            requested.iter().copied().filter(|chunk_id| missing.contains(chunk_id)).collect()
+        };
+        
+        // One request per peer, listing the wanted chunks that peer holds
+        let mut per_peer: BTreeMap<NodeId, Vec<u32>> = BTreeMap::new();
+        for chunk_id in wanted {
+            if let Some(holders) = state.chunk_availability.get(&(block_id, chunk_id)) {
+                for &peer in holders.iter().filter(|&&peer| peer != node) {
+                    per_peer.entry(peer).or_default().push(chunk_id);
+                }
+            }
+        }
+        
+        for (peer, chunk_ids) in per_peer {
+            let content = MessageContent::RepairRequest { block_id, chunk_ids };
+            self.handle_send_message(state, node, peer, content, MessagePriority::High);
+        }
+    }
+    
     fn handle_send_message(
         &self,
         state: &mut AlpenglowState,
@@ -2003,41 +3452,97 @@ impl AlpenglowState {
         content: MessageContent,
         priority: MessagePriority,
     ) {
-        // Check if nodes are in same partition
-        if let Some(partition) = &state.network_partition {
-            let from_in_a = partition.partition_a.contains(&from);
-            let to_in_a = partition.partition_a.contains(&to);
-            if from_in_a != to_in_a {
-                // Cross-partition message - drop it
-                return;
+        let tracked = priority == MessagePriority::Critical && state.network_state.reliable_delivery.is_some();
+        let message_id = self.send_message_with_delay(state, from, to, content.clone(), priority.clone(), 0);
+        if tracked {
+            let timeout = state.network_state.reliable_delivery.as_ref().map_or(0, |policy| policy.ack_timeout);
+            state.metrics.record_reliable(ReliableEvent::Sent);
+            state.message_queue.unacked.insert(message_id, UnackedMessage {
+                from,
+                to,
+                content,
+                priority,
+                retry_count: 0,
+                retransmit_at: state.global_time + timeout,
+            });
+            state.mark_reliable(message_id, message_id, 0);
+        }
+    }
+    
+    /// Resend reliable messages whose ack timed out, backing off exponentially up to the retry cap
+    fn retransmit_unacked(&self, state: &mut AlpenglowState) {
+        let Some(policy) = state.network_state.reliable_delivery.clone() else {
+            return;
+        };
+        let now = state.global_time;
+        let due: Vec<u64> = state.message_queue.unacked.iter()
+            .filter(|(_, unacked)| unacked.retransmit_at <= now)
+            .map(|(&id, _)| id)
+            .collect();
+        
+        for original_id in due {
+            let Some(mut unacked) = state.message_queue.unacked.remove(&original_id) else {
+                continue;
+            };
+            if unacked.retry_count >= policy.max_retries {
+                state.metrics.record_reliable(ReliableEvent::Abandoned);
+                continue;
             }
+            
+            unacked.retry_count += 1;
+            unacked.retransmit_at = now + policy.ack_timeout * policy.backoff_factor.pow(unacked.retry_count);
+            state.metrics.record_reliable(ReliableEvent::Retransmitted);
+            let message_id = self.send_message_with_delay(
+                state,
+                unacked.from,
+                unacked.to,
+                unacked.content.clone(),
+                unacked.priority.clone(),
+                0,
+            );
+            state.mark_reliable(message_id, original_id, unacked.retry_count);
+            state.message_queue.unacked.insert(original_id, unacked);
+        }
+    }
+    
+    fn send_message_with_delay(
+        &self,
+        state: &mut AlpenglowState,
+        from: NodeId,
+        to: NodeId,
+        content: MessageContent,
+        priority: MessagePriority,
+        extra_delay: Timestamp,
+    ) -> u64 {
+        // Every send attempt gets an id, so its random draws are independent of earlier losses
+        let message_id = state.message_queue.message_counter;
+        state.message_queue.message_counter += 1;
+        
+        // Under partial synchrony honest traffic is never lost, only held until its deadline
+        let ready = state.global_time + extra_delay;
+        let deliver_by = state.network_state.partial_synchrony.as_ref()
+            .filter(|_| state.status[&from] == NodeStatus::Honest && state.status[&to] == NodeStatus::Honest)
+            .map(|sync| sync.delivery_deadline(ready));
+        
+        // Cross-partition messages are dropped, except honest ones under partial synchrony,
+        // which are only held until their deadline
+        let mut should_drop = !state.can_node_communicate(from, to);
+        if should_drop && deliver_by.is_none() {
+            return message_id;
         }
         
         // Check for active network failures
-        let mut should_drop = false;
         let mut latency_multiplier = 1.0;
         
         for failure in &state.network_state.failure_injections {
-            if failure.start_time <= state.global_time && 
-               state.global_time < failure.start_time + failure.duration {
-                
+            if failure.is_active(state.global_time) {
                 match &failure.failure_type {
-                    FailureType::LinkFailure { from: f, to: t } => {
-                        if (*f == from && *t == to) || (*f == to && *t == from) {
-                            should_drop = true;
-                        }
-                    }
-                    FailureType::NodeIsolation { node } => {
-                        if *node == from || *node == to {
-                            should_drop = true;
-                        }
+                    FailureType::LinkFailure { from: f, to: t }
+                        if (*f == from && *t == to) || (*f == to && *t == from) => {
+                        should_drop = true;
                     }
-                    FailureType::PacketLoss { loss_rate } => {
-                        // Simple hash-based deterministic "randomness"
-                        let hash_val = (from + to + (state.global_time as u32)) % 100;
-                        if (hash_val as f64) / 100.0 < *loss_rate {
-                            should_drop = true;
-                        }
+                    FailureType::NodeIsolation { node } if *node == from || *node == to => {
+                        should_drop = true;
                     }
                     FailureType::LatencySpike { multiplier } => {
                         latency_multiplier *= multiplier;
@@ -2047,51 +3552,79 @@ impl AlpenglowState {
             }
         }
         
-        if should_drop {
-            return;
+        let loss_rate = state.packet_loss_rate();
+        should_drop |= match state.network_state.randomness {
+            NetworkRandomness::Seeded => loss_rate > 0.0 && state.network_state
+                .message_rng(message_id, LOSS_DRAW)
+                .gen_bool(loss_rate.min(1.0)),
+            // Partial loss is left to the checker's DropMessage branch
+            NetworkRandomness::Nondeterministic => loss_rate >= 1.0,
+        };
+        if should_drop && deliver_by.is_none() {
+            return message_id;
         }
         
         // Calculate delivery time based on latency model
-        let base_latency = self.calculate_latency(state, from, to);
+        let base_latency = self.message_latency(state, message_id, from, to);
         let final_latency = (base_latency as f64 * latency_multiplier) as u64;
-        
-        // Apply congestion effects
-        let congestion_factor = state.network_state.congestion_state
-            .current_utilization
-            .get(&(from, to))
-            .copied()
-            .unwrap_or(0.0);
-        
-        let congestion_delay = if congestion_factor > state.network_state.congestion_state.congestion_threshold {
-            (final_latency as f64 * congestion_factor) as u64
-        } else {
-            0
+        let mut latency_slack = match state.network_state.randomness {
+            NetworkRandomness::Nondeterministic => state.network_state.latency_model
+                .latency_range(from, to)
+                .map_or(0, |(min_ms, max_ms)| ((max_ms - min_ms) as f64 * latency_multiplier) as u64),
+            NetworkRandomness::Seeded => 0,
         };
         
-        let total_latency = final_latency + congestion_delay;
+        let total_latency = final_latency + extra_delay;
+        let mut scheduled_delivery_time = state.global_time + total_latency;
+        if let Some(deadline) = deliver_by {
+            // A dropped message arrives at the deadline; the adversary may delay any other up to it
+            scheduled_delivery_time = if should_drop { deadline } else { scheduled_delivery_time.min(deadline) };
+            latency_slack = deadline - scheduled_delivery_time;
+        }
+        let wire_size = content.wire_size();
         
         // Create pending message
-        let message_id = state.message_queue.message_counter;
-        state.message_queue.message_counter += 1;
-        
         let pending_message = PendingMessage {
             id: message_id,
             from,
             to,
             content,
             send_time: state.global_time,
-            scheduled_delivery_time: state.global_time + total_latency,
-            priority,
+            scheduled_delivery_time,
+            priority: priority.clone(),
             retry_count: 0,
+            latency_slack,
+            reliable_id: None,
+            deliver_by,
         };
         
         state.message_queue.pending_messages.push(pending_message);
+        state.update_link_utilization(from, to);
+        
+        // Throttled links send one message at a time, most urgent first
+        if let Some(bandwidth) = state.link_bandwidth(from, to) {
+            state.network_state.link_queues.entry((from, to)).or_default().push(LinkTransmission {
+                message_id,
+                priority,
+                ready,
+                duration: transmission_time(wire_size, bandwidth),
+                start: None,
+                finish: ready,
+            });
+            state.schedule_link(from, to);
+        }
+        message_id
     }
     
     fn handle_deliver_message(&self, state: &mut AlpenglowState, message_id: u64) {
         if let Some(pos) = state.message_queue.pending_messages.iter().position(|msg| msg.id == message_id) {
             let message = state.message_queue.pending_messages.remove(pos);
+            // A crashed node loses whatever reaches it
+            if matches!(state.status.get(&message.to), Some(NodeStatus::Crashed { .. })) {
+                return;
+            }
             let content_clone = message.content.clone();
+            state.record_heard(message.to, message.from);
             
             // Process the message content
             match &message.content {
@@ -2108,10 +3641,48 @@ impl AlpenglowState {
                 }
                 MessageContent::Certificate(cert) => {
                     // Deliver certificate
+                    let first = !state.slot_resolved(cert.slot);
                     state.certificates.insert(cert.slot, cert.clone());
+                    state.expire_relay_assignments(cert.slot);
+                    if first {
+                        state.record_slot_outcome(cert.slot, SlotOutcome::Finalized);
+                    }
+                }
+                // Chunks that fail verification are discarded
+                MessageContent::Chunk { chunk, from_leader } if state.is_authentic_chunk(chunk) => {
+                    state.record_chunk_receipt(message.to, chunk.block_id, chunk.chunk_id);
+                    
+                    // A relay forwards its assigned chunks to everyone else
+                    let assigned = state.relay_assignments
+                        .get(&(chunk.block_id, message.to))
+                        .is_some_and(|assignment| assignment.relay.assigned_chunks.contains(&chunk.chunk_id));
+                    if *from_leader && assigned {
+                        let targets: Vec<NodeId> = state.nodes.iter()
+                            .copied()
+                            .filter(|&node| node != message.from)
+                            .collect();
+                        self.relay_chunk(state, message.to, chunk.clone(), &targets);
+                    }
+                }
+                MessageContent::RepairRequest { block_id, chunk_ids } => {
+                    // Answer with every requested chunk this peer still holds
+                    let chunks: Vec<BlockChunk> = state.erasure_coded_blocks.get(block_id)
+                        .map(|erasure_block| erasure_block.chunks.iter()
+                            .filter(|chunk| chunk_ids.contains(&chunk.chunk_id))
+                            .cloned()
+                            .collect())
+                        .unwrap_or_default();
+                    for chunk in chunks {
+                        self.relay_chunk(state, message.to, chunk, &[message.from]);
+                    }
                 }
                 MessageContent::SkipCertificate(skip_cert) => {
+                    let first = !state.slot_resolved(skip_cert.slot);
                     state.skip_certs.insert(skip_cert.slot, skip_cert.clone());
+                    state.expire_relay_assignments(skip_cert.slot);
+                    if first {
+                        state.record_slot_outcome(skip_cert.slot, SlotOutcome::Skipped);
+                    }
                 }
                 MessageContent::CoalitionCoordination { coalition_id, instruction } => {
                     // Handle coalition coordination
@@ -2129,11 +3700,19 @@ impl AlpenglowState {
                         }
                     }
                 }
+                MessageContent::Ack { message_id } => {
+                    let acked = state.message_queue.unacked.get(message_id)
+                        .is_some_and(|unacked| unacked.to == message.from && unacked.from == message.to);
+                    if acked {
+                        state.message_queue.unacked.remove(message_id);
+                        state.metrics.record_reliable(ReliableEvent::Acknowledged);
+                    }
+                }
                 _ => {} // Heartbeat, gossip - just update delivery metrics
             }
             
             // Record successful delivery
-            let delivered_message = DeliveredMessage {
+            state.metrics.record_delivery(&DeliveredMessage {
                 id: message.id,
                 from: message.from,
                 to: message.to,
@@ -2141,27 +3720,53 @@ impl AlpenglowState {
                 send_time: message.send_time,
                 delivery_time: state.global_time,
                 actual_latency: state.global_time - message.send_time,
-            };
+            });
             
-            state.message_queue.delivered_messages.push(delivered_message);
+            // Acknowledge every copy of a reliable message, since earlier acks may have been lost
+            if let Some(original_id) = message.reliable_id {
+                state.metrics.record_reliable(ReliableEvent::AckSent);
+                let ack = MessageContent::Ack { message_id: original_id };
+                self.send_message_with_delay(state, message.to, message.from, ack, MessagePriority::High, 0);
+            }
         }
     }
     
+    /// Latency of the next message sent on `from -> to`, including queueing on a congested
+    /// link; nondeterministic runs get the lowest latency and branch on the rest through `DelayMessage`
     pub fn calculate_latency(&self, state: &AlpenglowState, from: NodeId, to: NodeId) -> u64 {
-        match &state.network_state.latency_model {
-            LatencyModel::Constant { latency_ms } => *latency_ms,
-            LatencyModel::Uniform { min_ms, max_ms } => {
-                // Simple hash-based deterministic "randomness"
-                let range = max_ms - min_ms;
-                if range == 0 { return *min_ms; }
-                let hash_val = (from + to + (state.global_time as u32)) % (range as u32);
-                min_ms + (hash_val as u64)
+        self.message_latency(state, state.message_queue.message_counter, from, to)
+    }
+    
+    /// Latency of message `message_id` on `from -> to`, drawn from the same stream as its loss
+    pub fn message_latency(&self, state: &AlpenglowState, message_id: u64, from: NodeId, to: NodeId) -> u64 {
+        let congestion = &state.network_state.congestion_state;
+        let utilization = congestion.current_utilization.get(&(from, to)).copied().unwrap_or(0.0);
+        let latency = self.model_latency(state, message_id, from, to);
+        
+        if utilization > congestion.congestion_threshold {
+            latency + (latency as f64 * utilization) as u64
+        } else {
+            latency
+        }
+    }
+    
+    fn model_latency(&self, state: &AlpenglowState, message_id: u64, from: NodeId, to: NodeId) -> u64 {
+        let network = &state.network_state;
+        if network.randomness == NetworkRandomness::Nondeterministic {
+            if let Some((min_ms, _)) = network.latency_model.latency_range(from, to) {
+                return min_ms;
             }
+        }
+        let mut rng = network.message_rng(message_id, LATENCY_DRAW);
+        
+        match &network.latency_model {
+            LatencyModel::Constant { latency_ms } => *latency_ms,
+            LatencyModel::Uniform { min_ms, max_ms } => rng.gen_range(*min_ms..=(*max_ms).max(*min_ms)),
             LatencyModel::Normal { mean_ms, std_dev_ms } => {
-                // Simplified normal distribution using hash
-                let hash_val = (from * 17 + to * 31 + (state.global_time * 7) as u32) % 1000;
-                let normalized = (hash_val as f64) / 1000.0; // 0.0 to 1.0
-                let z_score = (normalized - 0.5) * 4.0; // Rough normal distribution
+                // Box-Muller transform
+                let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+                let u2: f64 = rng.gen();
+                let z_score = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
                 let latency = (*mean_ms as f64) + (z_score * (*std_dev_ms as f64));
                 latency.max(1.0) as u64
             }
@@ -2177,6 +3782,10 @@ impl AlpenglowState {
                 
                 ((*base_latency_ms as f64) + distance_latency + congestion_latency) as u64
             }
+            LatencyModel::Matrix { topology } => {
+                let (min_ms, max_ms) = topology.latency_range(from, to);
+                rng.gen_range(min_ms..=max_ms)
+            }
         }
     }
 }
@@ -2283,12 +3892,7 @@ impl AlpenglowState {
                     .map(|c| c.members.len() as u32)
                     .sum(),
                 network_partitions: self.network_state.failure_injections.len() as u32,
-                average_latency: self.message_queue.delivered_messages
-                    .iter()
-                    .map(|m| m.actual_latency)
-                    .sum::<u64>()
-                    .checked_div(self.message_queue.delivered_messages.len() as u64)
-                    .unwrap_or(0),
+                average_latency: self.metrics.average_latency(),
             },
         }
     }
@@ -2344,7 +3948,10 @@ impl stateright::Model for AlpenglowModel {
     fn init_states(&self) -> Vec<Self::State> {
         let nodes = vec![0, 1, 2];
         let stake_dist = std::collections::HashMap::from([(0, 1000), (1, 1500), (2, 2000)]);
-        vec![AlpenglowState::new(nodes, stake_dist)]
+        let mut state = AlpenglowState::new(nodes, stake_dist);
+        // The checker explores loss and delay itself instead of sampling them
+        state.network_state.randomness = NetworkRandomness::Nondeterministic;
+        vec![state]
     }
 
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
@@ -2353,7 +3960,6 @@ impl stateright::Model for AlpenglowModel {
 
     fn next_state(&self, state: &Self::State, action: Self::Action) -> Option<Self::State> {
         // Use the Model trait implementation from AlpenglowState
-        use stateright::Model;
         state.next_state(state, action)
     }
 }
@@ -0,0 +1,382 @@
+// Worst-case adversary search: the checker picks every Byzantine vote per slot and recipient
+use crate::lib_improved::*;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Exhaustive search over Byzantine behavior within a stake budget. Byzantine nodes follow no
+/// fixed strategy: in every slot the checker decides which votes, if any, each of them sends
+/// to each honest node. Honest votes reach every honest node at once; crashed nodes never vote.
+///
+/// The search covers safety only. Only notarization votes are modeled, with no fast-path
+/// votes, timeouts or skip certificates and no message delays, so it cannot tell a stalled
+/// slot from one that would be skipped; liveness is left to the main model.
+///
+/// Honest nodes of equal stake are interchangeable, so states are deduplicated up to a
+/// relabeling of them. Each honest node still has 3 × 4 distinct (vote, Byzantine votes
+/// received) combinations per Byzantine node, slot and pair of blocks, so the state space
+/// grows with the number of distinct honest stakes. With equal stakes and one Byzantine node,
+/// seven validators take about 12k states for one slot and nine about 75k. With seven
+/// distinct stakes it is millions, past `max_states`, and the search reports itself
+/// inconclusive rather than run for minutes. `conflict_reachable` still decides such
+/// instances, without a trace.
+#[derive(Clone, Debug)]
+pub struct AdversarySearch {
+    pub stake_distribution: BTreeMap<NodeId, StakeAmount>,
+    pub byzantine: BTreeSet<NodeId>,
+    pub crashed: BTreeSet<NodeId>,
+    pub notarization_quorum: StakeAmount, // the model's slow quorum
+    pub slots: Slot,
+    pub blocks: Vec<BlockId>, // proposals honest nodes may vote for; several model an equivocating leader
+    pub max_states: usize,    // distinct states explored before the search gives up
+}
+
+/// States an `AdversarySearch` explores by default: seconds for a handful of validators, about
+/// ten for sixteen, where each state carries more views
+pub const DEFAULT_SEARCH_STATES: usize = 100_000;
+
+/// A vote as held in one honest node's view
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SearchVote {
+    pub slot: Slot,
+    pub voter: NodeId,
+    pub block: BlockId,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AdversarySearchState {
+    pub views: BTreeMap<NodeId, BTreeSet<SearchVote>>, // votes each honest node holds
+    pub honest_votes: BTreeMap<(NodeId, Slot), BlockId>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AdversaryAction {
+    HonestVote { node: NodeId, slot: Slot, block: BlockId },
+    ByzantineVote { from: NodeId, to: NodeId, slot: Slot, block: BlockId },
+}
+
+/// Shortest trace found to a property violation
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdversaryReport {
+    pub property: &'static str,
+    pub byzantine: BTreeSet<NodeId>,
+    pub byzantine_stake_fraction: f64,
+    pub trace: Vec<AdversaryAction>,
+}
+
+/// What a bounded search established
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdversaryOutcome {
+    pub violation: Option<AdversaryReport>,
+    pub states: usize,    // distinct states visited, up to symmetry
+    pub exhaustive: bool, // every reachable state was checked
+}
+
+impl AdversaryOutcome {
+    /// Whether no violation exists: `None` when the search stopped at `max_states` without one
+    pub fn holds(&self) -> Option<bool> {
+        match (&self.violation, self.exhaustive) {
+            (Some(_), _) => Some(false),
+            (None, true) => Some(true),
+            (None, false) => None,
+        }
+    }
+}
+
+impl AdversarySearch {
+    /// Search with the largest-stake validators that fit in `budget` of the total stake as Byzantine
+    pub fn new(base: &AlpenglowState, budget: f64, slots: Slot, blocks: Vec<BlockId>) -> Self {
+        Self {
+            stake_distribution: base.stake_distribution.iter().map(|(&node, &stake)| (node, stake)).collect(),
+            byzantine: base.faulty_nodes_for_fraction(budget).into_iter().collect(),
+            crashed: BTreeSet::new(),
+            notarization_quorum: base.slow_quorum_stake(),
+            slots,
+            blocks,
+            max_states: DEFAULT_SEARCH_STATES,
+        }
+    }
+
+    pub fn honest_nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.stake_distribution.keys().copied().filter(|node| !self.byzantine.contains(node) && !self.crashed.contains(node))
+    }
+
+    fn total_stake(&self) -> StakeAmount {
+        self.stake_distribution.values().sum()
+    }
+
+    pub fn byzantine_stake_fraction(&self) -> f64 {
+        let byzantine: StakeAmount = self.byzantine.iter().map(|node| self.stake_distribution[node]).sum();
+        byzantine as f64 / self.total_stake().max(1) as f64
+    }
+
+    /// Blocks `observer` can notarize in `slot`: those with a slow quorum of stake in its view
+    pub fn notarized_blocks(&self, state: &AdversarySearchState, observer: NodeId, slot: Slot) -> BTreeSet<BlockId> {
+        let mut block_stakes: BTreeMap<BlockId, StakeAmount> = BTreeMap::new();
+        for vote in state.views.get(&observer).into_iter().flatten().filter(|vote| vote.slot == slot) {
+            *block_stakes.entry(vote.block).or_default() += self.stake_distribution[&vote.voter];
+        }
+        block_stakes.into_iter()
+            .filter(|&(_, stake)| stake >= self.notarization_quorum)
+            .map(|(block, _)| block)
+            .collect()
+    }
+
+    /// Whether any reachable state violates `no_conflicting_notarization`, decided without a
+    /// search. Views only grow and a view only notarizes more as it grows, so the worst state
+    /// has every Byzantine vote delivered everywhere. Every view then holds the same votes, and
+    /// two blocks are notarized iff honest stake splits into two camps that each reach the
+    /// quorum with the Byzantine stake.
+    pub fn conflict_reachable(&self) -> bool {
+        if self.blocks.len() < 2 || self.honest_nodes().next().is_none() {
+            return false;
+        }
+        let byzantine: StakeAmount = self.byzantine.iter().map(|node| self.stake_distribution[node]).sum();
+        let needed = self.notarization_quorum.saturating_sub(byzantine);
+        let honest: StakeAmount = self.honest_nodes().map(|node| self.stake_distribution[&node]).sum();
+
+        // Stakes one camp can have; the other camp is everyone else
+        let mut camps: BTreeSet<StakeAmount> = BTreeSet::from([0]);
+        for node in self.honest_nodes() {
+            let stake = self.stake_distribution[&node];
+            camps = camps.iter().flat_map(|&camp| [camp, camp + stake]).collect();
+        }
+        camps.into_iter().any(|camp| camp >= needed && honest - camp >= needed)
+    }
+
+    /// Honest nodes grouped by stake; nodes in a group are interchangeable
+    fn stake_classes(&self) -> Vec<Vec<NodeId>> {
+        let mut classes: BTreeMap<StakeAmount, Vec<NodeId>> = BTreeMap::new();
+        for node in self.honest_nodes() {
+            classes.entry(self.stake_distribution[&node]).or_default().push(node);
+        }
+        classes.into_values().collect()
+    }
+
+    /// Identifies a state up to relabeling honest nodes of equal stake. A state is determined
+    /// by each honest node's own votes and the Byzantine votes it received, so the sorted
+    /// list of those per stake class is the same for every relabeling.
+    fn symmetry_key(&self, classes: &[Vec<NodeId>], state: &AdversarySearchState) -> Vec<Vec<(Vec<BlockId>, Vec<SearchVote>)>> {
+        classes.iter()
+            .map(|nodes| {
+                let mut signatures: Vec<(Vec<BlockId>, Vec<SearchVote>)> = nodes.iter()
+                    .map(|&node| {
+                        let own = (1..=self.slots)
+                            .map(|slot| state.honest_votes.get(&(node, slot)).copied().unwrap_or(0))
+                            .collect();
+                        let received = state.views[&node].iter()
+                            .filter(|vote| self.byzantine.contains(&vote.voter))
+                            .cloned()
+                            .collect();
+                        (own, received)
+                    })
+                    .collect();
+                signatures.sort();
+                signatures
+            })
+            .collect()
+    }
+
+    /// Shortest trace violating any property, if one exists
+    pub fn find_minimal_violation(&self) -> Option<AdversaryReport> {
+        self.search().violation
+    }
+
+    /// Breadth-first search for the shortest trace violating any property. States are explored
+    /// one depth at a time, so the first depth holding a violation gives a minimal trace; ties
+    /// go to the property name. Symmetric states are visited once, and the search stops after
+    /// `max_states` of them.
+    pub fn search(&self) -> AdversaryOutcome {
+        let properties = self.properties();
+        let classes = self.stake_classes();
+        let mut visited = HashSet::new();
+        let mut frontier: Vec<(AdversarySearchState, Vec<AdversaryAction>)> = Vec::new();
+        for state in self.init_states() {
+            if visited.insert(self.symmetry_key(&classes, &state)) {
+                frontier.push((state, Vec::new()));
+            }
+        }
+
+        while !frontier.is_empty() {
+            let violation = frontier.iter()
+                .flat_map(|(state, trace)| {
+                    properties.iter()
+                        .filter(|property| !(property.condition)(self, state))
+                        .map(move |property| (property.name, trace))
+                })
+                .min_by_key(|(property, _)| *property);
+            if let Some((property, trace)) = violation {
+                return AdversaryOutcome {
+                    violation: Some(AdversaryReport {
+                        property,
+                        byzantine: self.byzantine.clone(),
+                        byzantine_stake_fraction: self.byzantine_stake_fraction(),
+                        trace: trace.clone(),
+                    }),
+                    states: visited.len(),
+                    exhaustive: false,
+                };
+            }
+
+            let mut next = Vec::new();
+            for (state, trace) in frontier {
+                let mut actions = Vec::new();
+                self.actions(&state, &mut actions);
+                for action in actions {
+                    if let Some(successor) = self.next_state(&state, action.clone()) {
+                        if visited.insert(self.symmetry_key(&classes, &successor)) {
+                            if visited.len() > self.max_states {
+                                return AdversaryOutcome { violation: None, states: self.max_states, exhaustive: false };
+                            }
+                            let mut trace = trace.clone();
+                            trace.push(action);
+                            next.push((successor, trace));
+                        }
+                    }
+                }
+            }
+            frontier = next;
+        }
+        AdversaryOutcome { violation: None, states: visited.len(), exhaustive: true }
+    }
+}
+
+impl Model for AdversarySearch {
+    type State = AdversarySearchState;
+    type Action = AdversaryAction;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        vec![AdversarySearchState {
+            views: self.honest_nodes().map(|node| (node, BTreeSet::new())).collect(),
+            honest_votes: BTreeMap::new(),
+        }]
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        for slot in 1..=self.slots {
+            // Each honest node votes once per slot
+            for node in self.honest_nodes() {
+                if !state.honest_votes.contains_key(&(node, slot)) {
+                    for &block in &self.blocks {
+                        actions.push(AdversaryAction::HonestVote { node, slot, block });
+                    }
+                }
+            }
+
+            // Any Byzantine vote to any honest node, for any block
+            for &from in &self.byzantine {
+                for (&to, view) in &state.views {
+                    for &block in &self.blocks {
+                        if !view.contains(&SearchVote { slot, voter: from, block }) {
+                            actions.push(AdversaryAction::ByzantineVote { from, to, slot, block });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn next_state(&self, state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        let mut next = state.clone();
+        match action {
+            AdversaryAction::HonestVote { node, slot, block } => {
+                next.honest_votes.insert((node, slot), block);
+                for view in next.views.values_mut() {
+                    view.insert(SearchVote { slot, voter: node, block });
+                }
+            }
+            AdversaryAction::ByzantineVote { from, to, slot, block } => {
+                next.views.get_mut(&to)?.insert(SearchVote { slot, voter: from, block });
+            }
+        }
+        Some(next)
+    }
+
+    fn properties(&self) -> Vec<Property<Self>> {
+        vec![
+            // No two honest nodes notarize different blocks in the same slot
+            Property::always("no_conflicting_notarization", |search: &AdversarySearch, state: &AdversarySearchState| {
+                (1..=search.slots).all(|slot| {
+                    let notarized: BTreeSet<BlockId> = state.views.keys()
+                        .flat_map(|&observer| search.notarized_blocks(state, observer, slot))
+                        .collect();
+                    notarized.len() <= 1
+                })
+            }),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn search(budget: f64, blocks: Vec<BlockId>) -> AdversarySearch {
+        let nodes: Vec<NodeId> = (0..5).collect();
+        let stakes: HashMap<NodeId, StakeAmount> = nodes.iter().map(|&node| (node, 100)).collect();
+        AdversarySearch::new(&AlpenglowState::new(nodes, stakes), budget, 1, blocks)
+    }
+
+    #[test]
+    fn test_no_violation_without_byzantine_stake() {
+        let search = search(0.0, vec![1, 2]);
+        assert!(search.byzantine.is_empty());
+        assert_eq!(search.notarization_quorum, 300);
+        assert_eq!(search.find_minimal_violation(), None);
+        assert!(!search.conflict_reachable());
+    }
+
+    #[test]
+    fn test_finds_minimal_conflicting_notarization() {
+        // 20% Byzantine stake is exactly enough to tip two honest 40% camps to 60%
+        let search = search(0.2, vec![1, 2]);
+        assert_eq!(search.byzantine.len(), 1);
+
+        assert!(search.conflict_reachable());
+        let report = search.find_minimal_violation().unwrap();
+        assert_eq!(report.property, "no_conflicting_notarization");
+        assert_eq!(report.byzantine_stake_fraction, 0.2);
+        // Two honest votes per block, then a Byzantine vote for each block tips both to a quorum
+        assert_eq!(report.trace.len(), 6);
+        let byzantine_votes: BTreeSet<BlockId> = report.trace.iter()
+            .filter_map(|action| match action {
+                AdversaryAction::ByzantineVote { block, .. } => Some(*block),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(byzantine_votes, BTreeSet::from([1, 2]));
+    }
+
+    #[test]
+    fn test_single_proposal_cannot_conflict() {
+        // A Byzantine majority can withhold notarization, but with one block there is
+        // nothing to conflict with and the search reports no violation
+        let search = search(0.6, vec![1]);
+        assert_eq!(search.byzantine.len(), 3);
+        let outcome = search.search();
+        assert_eq!(outcome.violation, None);
+        assert_eq!(outcome.holds(), Some(true));
+        assert!(!search.conflict_reachable());
+    }
+
+    #[test]
+    fn test_symmetric_states_are_explored_once() {
+        // Four interchangeable honest nodes each vote for block 1 or not and hold the Byzantine
+        // vote for it or not: multisets of 4 over 4 signatures
+        let mut search = search(0.2, vec![1]);
+        let outcome = search.search();
+        assert_eq!(outcome.holds(), Some(true));
+        assert_eq!(outcome.states, 35);
+
+        // Relabeling honest nodes gives the same key
+        let init = search.init_states().remove(0);
+        let honest: Vec<NodeId> = search.honest_nodes().collect();
+        let vote = |node| search.next_state(&init, AdversaryAction::HonestVote { node, slot: 1, block: 1 }).unwrap();
+        let classes = search.stake_classes();
+        assert_eq!(search.symmetry_key(&classes, &vote(honest[0])), search.symmetry_key(&classes, &vote(honest[3])));
+
+        // A search cut short reports itself inconclusive
+        search.max_states = 10;
+        let outcome = search.search();
+        assert_eq!((outcome.holds(), outcome.exhaustive), (None, false));
+    }
+}
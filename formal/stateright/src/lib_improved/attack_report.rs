@@ -0,0 +1,223 @@
+// Attack accounting: what Byzantine behavior cost each slot, network-wide and per coalition
+use crate::lib_improved::*;
+
+/// Outcome of a coalition's attack so far, for the end of a simulation
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CoalitionAttackReport {
+    pub coalition: usize,
+    pub members: Vec<NodeId>,
+    pub stake_fraction: f64,
+    pub strategy: CoalitionAttackType,
+    pub phase: AttackPhase,
+    pub active: bool,
+    pub adaptations: u32,
+    pub metrics: AttackMetrics,
+}
+
+/// What Byzantine behavior cost one slot, against an all-honest run that finalizes every
+/// slot on the fast path
+#[derive(Clone, Debug, Default, PartialEq)]
+struct SlotDamage {
+    disrupted: bool,              // skipped instead of finalized
+    certificate_prevented: bool,  // no fast certificate
+    timeouts: u32,                // honest nodes that timed out on the slot
+    economic_damage: StakeAmount, // honest stake left without a finalized slot
+}
+
+impl AlpenglowState {
+    /// Charge the damage done to `slot` to every coalition executing an attack coordinated on
+    /// it, and to the network-wide metrics if there is one, then move those coalitions on: to
+    /// `Completion` if the slot suffered, to `Adaptation` if it finalized as if everyone were honest
+    pub(crate) fn record_attack_outcome(&mut self, slot: Slot, outcome: &SlotOutcome) {
+        let attackers: Vec<usize> = self.coalition_state.iter()
+            .filter(|(_, coalition_state)| coalition_state.active && coalition_state.current_phase == AttackPhase::Execution)
+            .map(|(&index, _)| index)
+            .filter(|&index| self.coalition_targets(index, slot))
+            .collect();
+        if attackers.is_empty() {
+            return;
+        }
+        let damage = self.slot_damage(slot, outcome);
+        let succeeded = damage.disrupted || damage.certificate_prevented;
+        let metrics = damage.metrics();
+
+        self.metrics.record_attack(&metrics);
+        for index in attackers {
+            let Some(coalition_state) = self.coalition_state.get_mut(&index) else {
+                continue;
+            };
+            coalition_state.success_metrics.accumulate(&metrics);
+            if succeeded {
+                coalition_state.current_phase = AttackPhase::Completion;
+            } else {
+                coalition_state.current_phase = AttackPhase::Adaptation;
+                coalition_state.adaptation_count += 1;
+            }
+        }
+    }
+
+    /// Whether coalition `index` coordinated an attack on `slot`
+    fn coalition_targets(&self, index: usize, slot: Slot) -> bool {
+        self.byzantine_coalitions.get(index)
+            .is_some_and(|coalition| coalition.coordination_history.iter().any(|event| event.slot == slot))
+    }
+
+    fn slot_damage(&self, slot: Slot, outcome: &SlotOutcome) -> SlotDamage {
+        let honest: Vec<NodeId> = self.nodes.iter()
+            .copied()
+            .filter(|node| matches!(self.status[node], NodeStatus::Honest))
+            .collect();
+        let timeouts = honest.iter()
+            .filter(|node| self.timeouts.get(node)
+                .and_then(|timeouts| timeouts.get(&slot))
+                .is_some_and(|info| info.count >= info.threshold))
+            .count() as u32;
+        let disrupted = *outcome == SlotOutcome::Skipped;
+        SlotDamage {
+            disrupted,
+            certificate_prevented: !self.certificates.get(&slot).is_some_and(|cert| cert.path == VotePath::Fast),
+            timeouts,
+            economic_damage: if disrupted { self.honest_stake() } else { 0 },
+        }
+    }
+
+    /// One report per coalition, in formation order
+    pub fn attack_reports(&self) -> Vec<CoalitionAttackReport> {
+        let total_stake = self.total_stake().max(1) as f64;
+        self.byzantine_coalitions.iter()
+            .enumerate()
+            .filter_map(|(index, coalition)| {
+                let coalition_state = self.coalition_state.get(&index)?;
+                Some(CoalitionAttackReport {
+                    coalition: index,
+                    members: coalition.members.clone(),
+                    stake_fraction: coalition.total_stake as f64 / total_stake,
+                    strategy: coalition.strategy.clone(),
+                    phase: coalition_state.current_phase.clone(),
+                    active: coalition_state.active,
+                    adaptations: coalition_state.adaptation_count,
+                    metrics: coalition_state.success_metrics.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl SlotDamage {
+    fn metrics(&self) -> AttackMetrics {
+        AttackMetrics {
+            slots_disrupted: self.disrupted as u32,
+            certificates_prevented: self.certificate_prevented as u32,
+            timeouts_caused: self.timeouts,
+            economic_damage: self.economic_damage,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Five equal validators; nodes 1 and 2 form a coalition that is executing its attack
+    fn coalition_state() -> AlpenglowState {
+        let nodes = vec![1, 2, 3, 4, 5];
+        let stake_distribution = nodes.iter().map(|&node| (node, 100)).collect();
+        let mut state = AlpenglowState::new(nodes, stake_distribution);
+        for node in [1, 2] {
+            state.status.insert(node, NodeStatus::Byzantine(ByzantineStrategy::WithholdVotes));
+        }
+        state = state.next_state(&state, AlpenglowAction::FormCoalition {
+            members: vec![1, 2],
+            strategy: CoalitionAttackType::CertificateManipulation {
+                target_path: VotePath::Fast,
+                manipulation_type: CertManipulationType::PreventCertification,
+            },
+        }).unwrap();
+        state.next_state(&state, AlpenglowAction::CoordinateAttack { coalition_index: 0, target_slot: 1 }).unwrap()
+    }
+
+    fn vote(state: AlpenglowState, node: NodeId, slot: Slot, path: VotePath) -> AlpenglowState {
+        state.next_state(&state, AlpenglowAction::Vote { node, slot, block: 0, path }).unwrap()
+    }
+
+    #[test]
+    fn test_skipped_slot_completes_attack() {
+        let mut state = coalition_state();
+        let analytics = Arc::new(Mutex::new(RunAnalytics::default()));
+        state.metrics = MetricsHandle::shared(analytics.clone());
+        // Honest nodes vote, then time out; their votes back the skip certificate
+        for node in 3..=5 {
+            state = vote(state, node, 1, VotePath::Slow);
+            for _ in 0..3 {
+                state = state.next_state(&state, AlpenglowAction::Timeout { node, slot: 1 }).unwrap();
+            }
+        }
+        state = state.next_state(&state, AlpenglowAction::SkipCert { slot: 1 }).unwrap();
+        assert!(state.skip_certs.contains_key(&1));
+
+        let expected = AttackMetrics {
+            slots_disrupted: 1,
+            certificates_prevented: 1,
+            timeouts_caused: 3,
+            economic_damage: 300,
+            ..Default::default()
+        };
+        assert_eq!(analytics.lock().unwrap().attack_metrics, expected);
+        let reports = state.attack_reports();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].members, vec![1, 2]);
+        assert_eq!(reports[0].stake_fraction, 0.4);
+        assert_eq!(reports[0].phase, AttackPhase::Completion);
+        assert_eq!(reports[0].metrics, expected);
+    }
+
+    #[test]
+    fn test_slow_finalization_prevents_fast_certificate() {
+        let mut state = coalition_state();
+        for node in 3..=5 {
+            state = vote(state, node, 1, VotePath::Slow);
+        }
+        state = state.next_state(&state, AlpenglowAction::Certify { slot: 1, path: VotePath::Slow }).unwrap();
+        assert_eq!(state.certificates[&1].path, VotePath::Slow);
+
+        let report = &state.attack_reports()[0];
+        assert_eq!(report.phase, AttackPhase::Completion);
+        assert_eq!(report.metrics.certificates_prevented, 1);
+        assert_eq!(report.metrics.slots_disrupted, 0);
+
+        // A later attack that cannot stop a fast certificate forces the coalition to adapt
+        state = state.next_state(&state, AlpenglowAction::CoordinateAttack { coalition_index: 0, target_slot: 2 }).unwrap();
+        // The members defect and vote honestly, so slot 2 gets its fast certificate
+        for node in 1..=5 {
+            state.status.insert(node, NodeStatus::Honest);
+            state = vote(state, node, 2, VotePath::Fast);
+        }
+        state = state.next_state(&state, AlpenglowAction::Certify { slot: 2, path: VotePath::Fast }).unwrap();
+        let report = &state.attack_reports()[0];
+        assert_eq!(report.phase, AttackPhase::Adaptation);
+        assert_eq!(report.adaptations, 1);
+        assert_eq!(report.metrics.certificates_prevented, 1);
+    }
+
+    #[test]
+    fn test_untargeted_slot_charges_nothing() {
+        let mut state = coalition_state();
+        let analytics = Arc::new(Mutex::new(RunAnalytics::default()));
+        state.metrics = MetricsHandle::shared(analytics.clone());
+        state = state.next_state(&state, AlpenglowAction::CoordinateAttack { coalition_index: 0, target_slot: 2 }).unwrap();
+
+        // Slot 3 was never targeted: its slow certificate is nobody's doing
+        for node in 3..=5 {
+            state = vote(state, node, 3, VotePath::Slow);
+        }
+        state = state.next_state(&state, AlpenglowAction::Certify { slot: 3, path: VotePath::Slow }).unwrap();
+        assert!(state.certificates.contains_key(&3));
+
+        let report = &state.attack_reports()[0];
+        assert_eq!(report.phase, AttackPhase::Execution);
+        assert_eq!(report.metrics, AttackMetrics::default());
+        assert_eq!(analytics.lock().unwrap().attack_metrics, AttackMetrics::default());
+    }
+}
@@ -0,0 +1,98 @@
+// Heartbeat failure detector: per-peer last-heard times and timeout-based suspicion
+use crate::lib_improved::*;
+use std::collections::{HashMap, HashSet};
+
+/// Every node's view of which peers have gone quiet; any delivered message, heartbeats
+/// included, counts as hearing from its sender
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FailureDetector {
+    pub timeout: Timestamp,    // silence after which a peer is suspected
+    pub started_at: Timestamp, // stands in for the last-heard time of peers not heard from yet
+    pub last_heard: HashMap<(NodeId, NodeId), Timestamp>, // (observer, peer) -> latest delivery
+    pub suspected: HashSet<(NodeId, NodeId)>,
+}
+
+/// A node started suspecting a peer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SuspicionEvent {
+    pub observer: NodeId,
+    pub peer: NodeId,
+    pub at: Timestamp,
+    pub last_heard: Timestamp,
+    pub peer_alive: bool,    // a false suspicion: the peer had not crashed
+    pub latency_spike: bool, // a LatencySpike failure was active
+}
+
+impl FailureDetector {
+    pub fn new(timeout: Timestamp, now: Timestamp) -> Self {
+        Self {
+            timeout,
+            started_at: now,
+            last_heard: HashMap::new(),
+            suspected: HashSet::new(),
+        }
+    }
+
+    pub fn last_heard(&self, observer: NodeId, peer: NodeId) -> Timestamp {
+        self.last_heard.get(&(observer, peer)).copied().unwrap_or(self.started_at)
+    }
+}
+
+impl AlpenglowState {
+    /// Whether `observer` currently suspects `peer`; never without a failure detector
+    pub fn suspects(&self, observer: NodeId, peer: NodeId) -> bool {
+        self.failure_detector.as_ref()
+            .is_some_and(|detector| detector.suspected.contains(&(observer, peer)))
+    }
+
+    /// Whether `node` suspects the leader of `slot`, and so can skip it without waiting
+    pub fn suspects_leader(&self, node: NodeId, slot: Slot) -> bool {
+        slot >= self.current_window.window_start && self.suspects(node, self.get_leader_for_slot(slot))
+    }
+
+    /// `observer` just heard from `peer`, which clears any suspicion
+    pub(crate) fn record_heard(&mut self, observer: NodeId, peer: NodeId) {
+        let now = self.global_time;
+        if let Some(detector) = &mut self.failure_detector {
+            detector.last_heard.insert((observer, peer), now);
+            detector.suspected.remove(&(observer, peer));
+        }
+    }
+
+    /// Suspect every peer silent for longer than the timeout; new suspicions go to the metrics sink
+    pub(crate) fn update_suspicions(&mut self) {
+        let Some(detector) = &self.failure_detector else {
+            return;
+        };
+        let now = self.global_time;
+        let latency_spike = self.network_state.failure_injections.iter()
+            .any(|failure| failure.is_active(now) && matches!(failure.failure_type, FailureType::LatencySpike { .. }));
+
+        let mut events = Vec::new();
+        for &observer in &self.nodes {
+            if matches!(self.status[&observer], NodeStatus::Crashed { .. }) {
+                continue;
+            }
+            for &peer in self.nodes.iter().filter(|&&peer| peer != observer) {
+                let last_heard = detector.last_heard(observer, peer);
+                if now.saturating_sub(last_heard) > detector.timeout && !detector.suspected.contains(&(observer, peer)) {
+                    events.push(SuspicionEvent {
+                        observer,
+                        peer,
+                        at: now,
+                        last_heard,
+                        peer_alive: !matches!(self.status[&peer], NodeStatus::Crashed { .. }),
+                        latency_spike,
+                    });
+                }
+            }
+        }
+
+        for event in events {
+            if let Some(detector) = &mut self.failure_detector {
+                detector.suspected.insert((event.observer, event.peer));
+            }
+            self.metrics.record_suspicion(&event);
+        }
+    }
+}
@@ -0,0 +1,378 @@
+// Analytics fed by transitions, kept out of the model state
+use crate::lib_improved::*;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Observer for what happens during a run; history lives here rather than in
+/// `AlpenglowState`, so states that differ only in their past compare equal
+pub trait MetricsSink: Send + fmt::Debug {
+    fn record_delivery(&mut self, _delivery: &DeliveredMessage) {}
+
+    /// A failure detector started suspecting a peer
+    fn record_suspicion(&mut self, _event: &SuspicionEvent) {}
+
+    fn record_reliable(&mut self, _event: ReliableEvent) {}
+
+    /// The leader started Rotor dissemination of `block_id`
+    fn record_block_dispatch(&mut self, _block_id: BlockId, _at: Timestamp) {}
+
+    /// `node` became able to reconstruct `block_id`
+    fn record_block_arrival(&mut self, _block_id: BlockId, _node: NodeId, _at: Timestamp) {}
+
+    /// A slot was finalized or skipped for the first time
+    fn record_slot_outcome(&mut self, _report: &SlotFaultReport) {}
+
+    /// Damage done by Byzantine behavior anywhere in the network, coalition members or not
+    fn record_attack(&mut self, _damage: &AttackMetrics) {}
+
+    /// Mean latency of the deliveries seen so far, 0 before the first one
+    fn average_latency(&self) -> u64 {
+        0
+    }
+}
+
+/// Constant-size latency statistics; the default sink
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LatencySummary {
+    pub deliveries: u64,
+    pub total_latency: u64,
+    pub max_latency: Timestamp,
+}
+
+impl MetricsSink for LatencySummary {
+    fn record_delivery(&mut self, delivery: &DeliveredMessage) {
+        self.deliveries += 1;
+        self.total_latency += delivery.actual_latency;
+        self.max_latency = self.max_latency.max(delivery.actual_latency);
+    }
+
+    fn average_latency(&self) -> u64 {
+        self.total_latency.checked_div(self.deliveries).unwrap_or(0)
+    }
+}
+
+/// Every delivery record, for tests and offline analysis
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeliveryLog {
+    pub deliveries: Vec<DeliveredMessage>,
+}
+
+impl DeliveryLog {
+    pub fn latency_samples(&self) -> impl Iterator<Item = Timestamp> + '_ {
+        self.deliveries.iter().map(|delivery| delivery.actual_latency)
+    }
+}
+
+impl MetricsSink for DeliveryLog {
+    fn record_delivery(&mut self, delivery: &DeliveredMessage) {
+        self.deliveries.push(delivery.clone());
+    }
+
+    fn average_latency(&self) -> u64 {
+        self.latency_samples().sum::<u64>()
+            .checked_div(self.deliveries.len() as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// Failure-detector suspicions, to measure how often live peers are suspected
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SuspicionLog {
+    pub events: Vec<SuspicionEvent>,
+}
+
+impl SuspicionLog {
+    /// Share of suspicions aimed at peers that had not crashed
+    pub fn false_suspicion_rate(&self) -> f64 {
+        Self::false_rate(self.events.iter())
+    }
+
+    /// False-suspicion rate among suspicions raised while a latency spike was active
+    pub fn false_suspicion_rate_during_spikes(&self) -> f64 {
+        Self::false_rate(self.events.iter().filter(|event| event.latency_spike))
+    }
+
+    fn false_rate<'a>(events: impl Iterator<Item = &'a SuspicionEvent>) -> f64 {
+        let (total, false_suspicions) = events.fold((0, 0), |(total, false_suspicions), event| {
+            (total + 1, false_suspicions + event.peer_alive as u32)
+        });
+        if total == 0 {
+            0.0
+        } else {
+            false_suspicions as f64 / total as f64
+        }
+    }
+}
+
+impl MetricsSink for SuspicionLog {
+    fn record_suspicion(&mut self, event: &SuspicionEvent) {
+        self.events.push(event.clone());
+    }
+}
+
+/// Reliable-delivery overhead, Rotor arrival times, the faults in effect when each slot
+/// resolved and the damage attacks did, for one run
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunAnalytics {
+    pub reliable: ReliableDeliveryStats,
+    pub block_dispatch_times: HashMap<BlockId, Timestamp>, // when the leader started Rotor dissemination
+    pub block_arrival_times: HashMap<(BlockId, NodeId), Timestamp>, // when each node could first reconstruct
+    pub slot_fault_reports: BTreeMap<Slot, SlotFaultReport>,
+    pub attack_metrics: AttackMetrics,
+}
+
+impl RunAnalytics {
+    /// Rotor latency from dispatch until each node could reconstruct `block_id`
+    pub fn block_arrival_latencies(&self, block_id: BlockId) -> HashMap<NodeId, Timestamp> {
+        let Some(&dispatched) = self.block_dispatch_times.get(&block_id) else {
+            return HashMap::new();
+        };
+        self.block_arrival_times.iter()
+            .filter(|((bid, _), _)| *bid == block_id)
+            .map(|(&(_, node), &arrival)| (node, arrival.saturating_sub(dispatched)))
+            .collect()
+    }
+
+    /// Time until nodes holding at least `percent`% of `state`'s stake could reconstruct `block_id`
+    pub fn stake_weighted_arrival_latency(&self, state: &AlpenglowState, block_id: BlockId, percent: u64) -> Option<Timestamp> {
+        let mut latencies: Vec<(Timestamp, NodeId)> = self.block_arrival_latencies(block_id)
+            .into_iter()
+            .map(|(node, latency)| (latency, node))
+            .collect();
+        latencies.sort();
+
+        let required = (percent * state.total_stake()).div_ceil(100);
+        let mut reached = 0;
+        for (latency, node) in latencies {
+            reached += *state.stake_distribution.get(&node).unwrap_or(&0);
+            if reached >= required {
+                return Some(latency);
+            }
+        }
+        None
+    }
+
+    /// Whether Rotor delivered `block_id` to 80%/60% of stake within the δ assumptions
+    pub fn rotor_meets_delta_bounds(&self, state: &AlpenglowState, block_id: BlockId) -> bool {
+        let within = |percent, delta| {
+            self.stake_weighted_arrival_latency(state, block_id, percent)
+                .is_some_and(|latency| latency <= delta)
+        };
+        within(80, DELTA_80_MS) && within(60, DELTA_60_MS)
+    }
+}
+
+impl MetricsSink for RunAnalytics {
+    fn record_reliable(&mut self, event: ReliableEvent) {
+        self.reliable.record(event);
+    }
+
+    fn record_block_dispatch(&mut self, block_id: BlockId, at: Timestamp) {
+        self.block_dispatch_times.insert(block_id, at);
+    }
+
+    fn record_block_arrival(&mut self, block_id: BlockId, node: NodeId, at: Timestamp) {
+        self.block_arrival_times.entry((block_id, node)).or_insert(at);
+    }
+
+    fn record_slot_outcome(&mut self, report: &SlotFaultReport) {
+        self.slot_fault_reports.entry(report.slot).or_insert_with(|| report.clone());
+    }
+
+    fn record_attack(&mut self, damage: &AttackMetrics) {
+        self.attack_metrics.accumulate(damage);
+    }
+}
+
+/// Forwards every record to several sinks, so one analysis does not displace another; each
+/// sink must appear once, since it is locked while the record is forwarded
+#[derive(Debug, Default)]
+pub struct FanOut {
+    pub sinks: Vec<Arc<Mutex<dyn MetricsSink>>>,
+}
+
+impl FanOut {
+    fn each(&mut self, mut record: impl FnMut(&mut dyn MetricsSink)) {
+        for sink in &self.sinks {
+            record(&mut *sink.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+        }
+    }
+}
+
+impl MetricsSink for FanOut {
+    fn record_delivery(&mut self, delivery: &DeliveredMessage) {
+        self.each(|sink| sink.record_delivery(delivery));
+    }
+
+    fn record_suspicion(&mut self, event: &SuspicionEvent) {
+        self.each(|sink| sink.record_suspicion(event));
+    }
+
+    fn record_reliable(&mut self, event: ReliableEvent) {
+        self.each(|sink| sink.record_reliable(event));
+    }
+
+    fn record_block_dispatch(&mut self, block_id: BlockId, at: Timestamp) {
+        self.each(|sink| sink.record_block_dispatch(block_id, at));
+    }
+
+    fn record_block_arrival(&mut self, block_id: BlockId, node: NodeId, at: Timestamp) {
+        self.each(|sink| sink.record_block_arrival(block_id, node, at));
+    }
+
+    fn record_slot_outcome(&mut self, report: &SlotFaultReport) {
+        self.each(|sink| sink.record_slot_outcome(report));
+    }
+
+    fn record_attack(&mut self, damage: &AttackMetrics) {
+        self.each(|sink| sink.record_attack(damage));
+    }
+
+    /// Average of the first sink that tracks latency
+    fn average_latency(&self) -> u64 {
+        self.sinks.iter()
+            .map(|sink| sink.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).average_latency())
+            .find(|&latency| latency > 0)
+            .unwrap_or(0)
+    }
+}
+
+/// Shared handle to a sink; every state cloned from the same origin reports to the same sink,
+/// and the handle never affects state equality.
+///
+/// A checker explores many paths from one initial state, so during a check the sink mixes
+/// records from every path it visited, and `average_latency` in `to_compact_state` is an
+/// average over all of them. For figures that describe a single run, install a fresh sink
+/// and drive that run alone.
+#[derive(Clone)]
+pub struct MetricsHandle(Arc<Mutex<dyn MetricsSink>>);
+
+impl MetricsHandle {
+    pub fn new(sink: impl MetricsSink + 'static) -> Self {
+        Self(Arc::new(Mutex::new(sink)))
+    }
+
+    /// Report into a sink the caller keeps a typed reference to
+    pub fn shared(sink: Arc<Mutex<dyn MetricsSink>>) -> Self {
+        Self(sink)
+    }
+
+    /// Report into every one of `sinks`
+    pub fn fan_out(sinks: Vec<Arc<Mutex<dyn MetricsSink>>>) -> Self {
+        Self::new(FanOut { sinks })
+    }
+
+    fn sink(&self) -> MutexGuard<'_, dyn MetricsSink + 'static> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn record_delivery(&self, delivery: &DeliveredMessage) {
+        self.sink().record_delivery(delivery);
+    }
+
+    pub fn record_suspicion(&self, event: &SuspicionEvent) {
+        self.sink().record_suspicion(event);
+    }
+
+    pub fn record_reliable(&self, event: ReliableEvent) {
+        self.sink().record_reliable(event);
+    }
+
+    pub fn record_block_dispatch(&self, block_id: BlockId, at: Timestamp) {
+        self.sink().record_block_dispatch(block_id, at);
+    }
+
+    pub fn record_block_arrival(&self, block_id: BlockId, node: NodeId, at: Timestamp) {
+        self.sink().record_block_arrival(block_id, node, at);
+    }
+
+    pub fn record_slot_outcome(&self, report: &SlotFaultReport) {
+        self.sink().record_slot_outcome(report);
+    }
+
+    pub fn record_attack(&self, damage: &AttackMetrics) {
+        self.sink().record_attack(damage);
+    }
+
+    pub fn average_latency(&self) -> u64 {
+        self.sink().average_latency()
+    }
+}
+
+impl Default for MetricsHandle {
+    fn default() -> Self {
+        Self::new(LatencySummary::default())
+    }
+}
+
+impl fmt::Debug for MetricsHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsHandle")
+    }
+}
+
+impl PartialEq for MetricsHandle {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery(id: u64, latency: Timestamp) -> DeliveredMessage {
+        DeliveredMessage {
+            id,
+            from: 0,
+            to: 1,
+            content: MessageContent::Heartbeat { sequence: id },
+            send_time: 100,
+            delivery_time: 100 + latency,
+            actual_latency: latency,
+        }
+    }
+
+    #[test]
+    fn test_sinks_agree_on_average_latency() {
+        let mut summary = LatencySummary::default();
+        let mut log = DeliveryLog::default();
+        assert_eq!(summary.average_latency(), 0);
+        assert_eq!(log.average_latency(), 0);
+
+        for (id, latency) in [(0, 40), (1, 60), (2, 80)] {
+            summary.record_delivery(&delivery(id, latency));
+            log.record_delivery(&delivery(id, latency));
+        }
+        assert_eq!(summary.average_latency(), 60);
+        assert_eq!(log.average_latency(), 60);
+        assert_eq!(summary.max_latency, 80);
+        assert_eq!(log.latency_samples().collect::<Vec<_>>(), vec![40, 60, 80]);
+    }
+
+    #[test]
+    fn test_clones_share_one_sink() {
+        let log = Arc::new(Mutex::new(DeliveryLog::default()));
+        let handle = MetricsHandle::shared(log.clone());
+        let clone = handle.clone();
+
+        handle.record_delivery(&delivery(0, 10));
+        clone.record_delivery(&delivery(1, 30));
+        assert_eq!(log.lock().unwrap().deliveries.len(), 2);
+        assert_eq!(handle.average_latency(), 20);
+    }
+
+    #[test]
+    fn test_fan_out_keeps_latency_beside_suspicions() {
+        let summary = Arc::new(Mutex::new(LatencySummary::default()));
+        let suspicions = Arc::new(Mutex::new(SuspicionLog::default()));
+        let handle = MetricsHandle::fan_out(vec![suspicions.clone(), summary.clone()]);
+
+        handle.record_delivery(&delivery(0, 40));
+        handle.record_suspicion(&SuspicionEvent { observer: 0, peer: 1, at: 150, last_heard: 40, peer_alive: true, latency_spike: false });
+        assert_eq!(handle.average_latency(), 40);
+        assert_eq!(summary.lock().unwrap().deliveries, 1);
+        assert_eq!(suspicions.lock().unwrap().events.len(), 1);
+    }
+}
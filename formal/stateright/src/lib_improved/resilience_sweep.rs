@@ -0,0 +1,233 @@
+// Resilience threshold sweep: where safety, liveness and the fast path break as faults grow
+use crate::lib_improved::*;
+use std::collections::{BTreeSet, HashSet};
+
+/// Liveness properties the sweep reports on. The model gates them on faults staying within
+/// its thresholds, so the sweep checks them without that guard to see where they break.
+/// Safety comes from the adversary search and the fast path from `fast_certificate`.
+pub const SWEPT_PROPERTIES: [&str; 2] = [
+    "crash_fault_liveness",
+    "crash_fault_skip_liveness",
+];
+
+/// Which kind of fault the sweep grows
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SweepFault {
+    Byzantine, // faulty nodes equivocate in the model; in the adversary search they send anything
+    Crash,     // faulty nodes crash before the slot starts
+}
+
+/// Outcome of one sweep point
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepPoint {
+    pub requested_fraction: f64,
+    pub fault_fraction: f64, // stake actually faulty; node stakes make it jump in steps
+    pub faulty: Vec<NodeId>,
+    /// Faulty stake is below `byzantine_threshold_stake` or within `crash_threshold_stake`
+    pub within_thresholds: bool,
+    /// Stake-weighted safety, judged on each honest node's view by the adversary search: no
+    /// Byzantine behavior gets two honest nodes to notarize different blocks in a slot
+    pub no_conflicting_notarization: bool,
+    /// Live honest nodes alone produce a slow, a skip and a fast certificate for slot 1
+    pub slow_certificate: bool,
+    pub skip_certificate: bool,
+    pub fast_certificate: bool,
+    /// Swept model properties the simulator found violated
+    pub violated_properties: BTreeSet<&'static str>,
+}
+
+/// Sweep of one fault kind over a configured instance, from 0 to `max_fraction` of the stake
+/// in `step` increments. Every quorum comes from the model's stake thresholds, so the points
+/// where guarantees break move with them.
+#[derive(Clone, Debug)]
+pub struct ResilienceSweep {
+    pub base: AlpenglowState,
+    pub fault: SweepFault,
+    pub step: f64,
+    pub max_fraction: f64,
+    pub simulation_states: usize, // states the simulator visits per point
+    pub seed: u64,
+}
+
+impl ResilienceSweep {
+    pub fn new(base: AlpenglowState, fault: SweepFault, step: f64) -> Self {
+        Self { base, fault, step, max_fraction: 0.4, simulation_states: 2_000, seed: 0 }
+    }
+
+    /// Requested fault fractions, both ends included
+    pub fn fractions(&self) -> Vec<f64> {
+        let steps = (self.max_fraction / self.step.max(f64::EPSILON) + 1e-9).floor() as usize;
+        (0..=steps).map(|i| i as f64 * self.step).collect()
+    }
+
+    pub fn run(&self) -> Vec<SweepPoint> {
+        self.fractions().into_iter().map(|fraction| self.run_point(fraction)).collect()
+    }
+
+    /// Make the largest-stake nodes within `fraction` of the stake faulty and check slot 1.
+    /// The main model keeps one certificate per slot, so conflicting notarizations are looked
+    /// for by the adversary search, which tracks each honest node's view.
+    pub fn run_point(&self, fraction: f64) -> SweepPoint {
+        let faulty = self.base.faulty_nodes_for_fraction(fraction);
+        let faulty_stake: StakeAmount = faulty.iter().map(|node| self.base.stake_distribution[node]).sum();
+
+        let mut search = AdversarySearch::new(&self.base, 0.0, 1, vec![1, 2]);
+        match self.fault {
+            SweepFault::Byzantine => search.byzantine = faulty.iter().copied().collect(),
+            SweepFault::Crash => search.crashed = faulty.iter().copied().collect(),
+        }
+        let no_conflicting_notarization = !search.conflict_reachable();
+
+        let state = self.faulty_state(&faulty, fraction);
+        let mut faulty: Vec<NodeId> = faulty.into_iter().collect();
+        faulty.sort();
+        SweepPoint {
+            requested_fraction: fraction,
+            fault_fraction: faulty_stake as f64 / self.base.total_stake().max(1) as f64,
+            faulty,
+            within_thresholds: state.faults_within_thresholds(),
+            no_conflicting_notarization,
+            slow_certificate: state.honest_certification_completes(1, VotePath::Slow),
+            skip_certificate: state.honest_skip_completes(1),
+            fast_certificate: state.honest_certification_completes(1, VotePath::Fast),
+            violated_properties: self.simulate(state),
+        }
+    }
+
+    /// First point at which `holds` is false
+    pub fn first_failure(points: &[SweepPoint], holds: impl Fn(&SweepPoint) -> bool) -> Option<&SweepPoint> {
+        points.iter().find(|point| !holds(point))
+    }
+
+    /// The model's threshold for this fault kind, as a fraction of the stake: Byzantine stake
+    /// must stay below it, crashed stake may reach it
+    pub fn threshold_fraction(&self) -> f64 {
+        let threshold = match self.fault {
+            SweepFault::Byzantine => self.base.byzantine_threshold_stake(),
+            SweepFault::Crash => self.base.crash_threshold_stake(),
+        };
+        threshold as f64 / self.base.total_stake().max(1) as f64
+    }
+
+    fn faulty_state(&self, faulty: &HashSet<NodeId>, fraction: f64) -> AlpenglowState {
+        let mut state = self.base.clone();
+        match self.fault {
+            SweepFault::Byzantine => {
+                for &node in faulty {
+                    state.status.insert(node, NodeStatus::Byzantine(ByzantineStrategy::Equivocation));
+                }
+            }
+            SweepFault::Crash => {
+                state.crash_budget = fraction;
+                for &node in faulty {
+                    state = state.next_state(&state, AlpenglowAction::Crash { node }).unwrap_or(state);
+                }
+            }
+        }
+        state
+    }
+
+    /// Swept properties violated on random runs of the model from `state`
+    fn simulate(&self, state: AlpenglowState) -> BTreeSet<&'static str> {
+        UngatedLiveness(state).checker()
+            .target_state_count(self.simulation_states)
+            .spawn_simulation(self.seed, UniformChooser)
+            .join()
+            .discoveries()
+            .into_keys()
+            .filter(|property| SWEPT_PROPERTIES.contains(property))
+            .collect()
+    }
+}
+
+/// The model with its crash liveness properties checked whatever the faults
+struct UngatedLiveness(AlpenglowState);
+
+impl Model for UngatedLiveness {
+    type State = AlpenglowState;
+    type Action = AlpenglowAction;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        vec![self.0.clone()]
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        self.0.actions(state, actions)
+    }
+
+    fn next_state(&self, state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        self.0.next_state(state, action)
+    }
+
+    fn properties(&self) -> Vec<Property<Self>> {
+        vec![
+            Property::always("crash_fault_liveness", |_, state: &Self::State| state.current_slot_resolvable()),
+            Property::always("crash_fault_skip_liveness", |_, state: &Self::State| state.current_slot_skippable()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn sweep(fault: SweepFault) -> Vec<SweepPoint> {
+        sweep_to(fault, 0.4)
+    }
+
+    fn sweep_to(fault: SweepFault, max_fraction: f64) -> Vec<SweepPoint> {
+        let nodes: Vec<NodeId> = (0..5).collect();
+        let stakes: HashMap<NodeId, StakeAmount> = nodes.iter().map(|&node| (node, 100)).collect();
+        let mut sweep = ResilienceSweep::new(AlpenglowState::new(nodes, stakes), fault, 0.1);
+        sweep.max_fraction = max_fraction;
+        sweep.simulation_states = 200;
+        assert_eq!(sweep.threshold_fraction(), 0.2);
+        sweep.run()
+    }
+
+    #[test]
+    fn test_byzantine_sweep_breaks_safety_at_the_threshold() {
+        let points = sweep(SweepFault::Byzantine);
+        assert_eq!(points.len(), 5);
+        assert!(points[..2].iter().all(|point| point.fault_fraction == 0.0 && point.no_conflicting_notarization));
+
+        // One Byzantine node in five tips two honest camps to a quorum each; 20% is already
+        // past the strict byzantine_threshold_stake
+        assert!(points[..2].iter().all(|point| point.within_thresholds));
+        let conflict = ResilienceSweep::first_failure(&points, |point| point.no_conflicting_notarization).unwrap();
+        assert_eq!(conflict.fault_fraction, 0.2);
+        assert!(!conflict.within_thresholds);
+        assert!(!points[4].within_thresholds);
+
+        // The other four still meet the fast quorum exactly; three meet only the slow quorum
+        let fast = ResilienceSweep::first_failure(&points, |point| point.fast_certificate).unwrap();
+        assert_eq!(fast.fault_fraction, 0.4);
+        assert!(points.iter().all(|point| point.slow_certificate && point.skip_certificate));
+        assert!(points.iter().all(|point| point.violated_properties.is_empty()));
+    }
+
+    #[test]
+    fn test_crash_sweep_keeps_safety_and_liveness_to_forty_percent() {
+        let points = sweep(SweepFault::Crash);
+        assert!(points.iter().all(|point| point.no_conflicting_notarization && point.slow_certificate && point.skip_certificate));
+        assert_eq!(points[4].faulty, vec![0, 1]);
+        assert!(points[2].within_thresholds && !points[4].within_thresholds);
+
+        let fast = ResilienceSweep::first_failure(&points, |point| point.fast_certificate).unwrap();
+        assert_eq!(fast.fault_fraction, 0.4);
+        assert!(points.iter().all(|point| point.violated_properties.is_empty()));
+    }
+
+    #[test]
+    fn test_crash_sweep_finds_liveness_failures_past_the_quorum() {
+        // With 60% crashed the other 40% reach neither quorum, and the swept properties,
+        // unlike the model's gated ones, say so
+        let points = sweep_to(SweepFault::Crash, 0.6);
+        assert_eq!(points.len(), 7);
+        assert!(points[..5].iter().all(|point| point.violated_properties.is_empty()));
+        assert!(!points[6].slow_certificate && !points[6].skip_certificate);
+        assert_eq!(points[6].violated_properties, BTreeSet::from(SWEPT_PROPERTIES));
+        assert!(points[6].no_conflicting_notarization);
+    }
+}
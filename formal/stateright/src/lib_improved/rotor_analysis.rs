@@ -0,0 +1,304 @@
+// Rotor reconstruction-failure risk analysis
+use crate::lib_improved::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Probability that honest nodes cannot reconstruct a block because too many of its
+/// relays are faulty, computed exactly and cross-checked by sampling
+#[derive(Clone, Debug)]
+pub struct RotorFailureAnalysis {
+    pub sampling: RelaySampling,
+    pub relay_count: usize,     // Γ: one relay per chunk
+    pub required_chunks: usize, // γ: chunks needed to reconstruct
+    pub faulty_stake_fraction: f64,
+    /// None when the exact computation would track too many partial relay draws
+    pub exact_probability: Option<f64>,
+    pub monte_carlo_probability: f64,
+    pub monte_carlo_trials: u32,
+}
+
+impl AlpenglowState {
+    /// Largest-stake-first set of nodes whose combined stake stays within `fraction` of the total
+    pub fn faulty_nodes_for_fraction(&self, fraction: f64) -> HashSet<NodeId> {
+        let budget = (self.total_stake() as f64 * fraction).floor() as StakeAmount;
+        let mut by_stake = self.relay_candidates();
+        by_stake.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let mut used = 0;
+        let mut faulty = HashSet::new();
+        for (node, stake) in by_stake {
+            if used + stake <= budget {
+                used += stake;
+                faulty.insert(node);
+            }
+        }
+        faulty
+    }
+
+    /// Reconstruction-failure probability for `erasure_block` when `faulty` relays drop their chunks
+    pub fn analyze_rotor_failure(
+        &self,
+        faulty: &HashSet<NodeId>,
+        erasure_block: &ErasureCodedBlock,
+        trials: u32,
+    ) -> RotorFailureAnalysis {
+        let relay_count = erasure_block.chunks.len();
+        let required_chunks = erasure_block.required_chunks;
+        let faulty_stake: StakeAmount = faulty.iter()
+            .map(|node| *self.stake_distribution.get(node).unwrap_or(&0))
+            .sum();
+
+        RotorFailureAnalysis {
+            sampling: self.relay_sampling.clone(),
+            relay_count,
+            required_chunks,
+            faulty_stake_fraction: faulty_stake as f64 / self.total_stake().max(1) as f64,
+            exact_probability: self.exact_rotor_failure_probability(faulty, relay_count, required_chunks),
+            monte_carlo_probability: self.monte_carlo_rotor_failure_probability(faulty, erasure_block, trials),
+            monte_carlo_trials: trials,
+        }
+    }
+
+    /// Exact failure probability: binomial for independent draws, stake-weighted
+    /// hypergeometric for draws without replacement, Poisson-binomial over the residual bins
+    /// for partition sampling
+    pub fn exact_rotor_failure_probability(
+        &self,
+        faulty: &HashSet<NodeId>,
+        relay_count: usize,
+        required_chunks: usize,
+    ) -> Option<f64> {
+        if required_chunks > relay_count {
+            return Some(1.0);
+        }
+        let tolerated = relay_count - required_chunks;
+        let candidates = self.relay_candidates();
+        let total: u128 = candidates.iter().map(|&(_, stake)| stake as u128).sum();
+        if total == 0 {
+            return Some(1.0);
+        }
+
+        match self.relay_sampling {
+            RelaySampling::WithReplacement => {
+                let faulty_stake: u128 = candidates.iter()
+                    .filter(|(node, _)| faulty.contains(node))
+                    .map(|&(_, stake)| stake as u128)
+                    .sum();
+                let p = faulty_stake as f64 / total as f64;
+                Some(poisson_binomial_tail(&vec![p; relay_count], 0, tolerated))
+            }
+            RelaySampling::PartitionSampling => {
+                let (fixed, bins) = Self::partition_bins(&candidates, relay_count);
+                let fixed_faulty = fixed.iter().filter(|node| faulty.contains(node)).count();
+                let probabilities: Vec<f64> = bins.iter()
+                    .map(|bin| {
+                        let size: u128 = bin.iter().map(|&(_, amount)| amount).sum();
+                        let bad: u128 = bin.iter()
+                            .filter(|(node, _)| faulty.contains(node))
+                            .map(|&(_, amount)| amount)
+                            .sum();
+                        bad as f64 / size as f64
+                    })
+                    .collect();
+                Some(poisson_binomial_tail(&probabilities, fixed_faulty, tolerated))
+            }
+            RelaySampling::WithoutReplacement => {
+                // Every full round draws each candidate once; only the last round is random
+                let faulty_candidates = candidates.iter().filter(|(node, _)| faulty.contains(node)).count();
+                let fixed_faulty = relay_count / candidates.len() * faulty_candidates;
+                let distribution = faulty_draw_distribution(&candidates, faulty, relay_count % candidates.len())?;
+                Some(distribution.iter()
+                    .enumerate()
+                    .filter(|(k, _)| fixed_faulty + k > tolerated)
+                    .map(|(_, mass)| mass)
+                    .sum())
+            }
+        }
+    }
+
+    /// Fraction of sampled relay sets, via `select_relay_nodes`, leaving fewer than γ honest chunks
+    pub fn monte_carlo_rotor_failure_probability(
+        &self,
+        faulty: &HashSet<NodeId>,
+        erasure_block: &ErasureCodedBlock,
+        trials: u32,
+    ) -> f64 {
+        let leaders: Vec<NodeId> = self.relay_candidates().into_iter().map(|(node, _)| node).collect();
+        if trials == 0 || leaders.is_empty() {
+            return 0.0;
+        }
+
+        let mut failures = 0u32;
+        for trial in 0..trials {
+            // Every trial gets its own (slot, leader) seed
+            let leader = leaders[trial as usize % leaders.len()];
+            let slot = trial / leaders.len() as u32 + 1;
+            let honest_chunks: usize = self.select_relay_nodes(slot, leader, erasure_block)
+                .iter()
+                .filter(|relay| !faulty.contains(&relay.node_id))
+                .map(|relay| relay.assigned_chunks.len())
+                .sum();
+            if honest_chunks < erasure_block.required_chunks {
+                failures += 1;
+            }
+        }
+        failures as f64 / trials as f64
+    }
+}
+
+/// Partial draws the exact without-replacement computation may track at once
+const EXACT_DRAW_STATE_LIMIT: usize = 1 << 20;
+
+/// Distribution of the number of faulty nodes among `draws` stake-weighted draws without
+/// replacement; with equal stakes this is the hypergeometric distribution. Nodes with the
+/// same stake and status are interchangeable, so only how many of each are left is tracked.
+/// None if that needs more than `EXACT_DRAW_STATE_LIMIT` states.
+fn faulty_draw_distribution(candidates: &[(NodeId, StakeAmount)], faulty: &HashSet<NodeId>, draws: usize) -> Option<Vec<f64>> {
+    let mut classes: BTreeMap<(StakeAmount, bool), usize> = BTreeMap::new();
+    for &(node, stake) in candidates {
+        *classes.entry((stake, faulty.contains(&node))).or_default() += 1;
+    }
+    let classes: Vec<((StakeAmount, bool), usize)> = classes.into_iter().collect();
+
+    // Remaining nodes per class -> probability of having drawn exactly the others
+    let mut states: HashMap<Vec<usize>, f64> = HashMap::from([(classes.iter().map(|&(_, count)| count).collect(), 1.0)]);
+    for _ in 0..draws {
+        let mut next: HashMap<Vec<usize>, f64> = HashMap::new();
+        for (remaining, mass) in states {
+            let weight: f64 = classes.iter()
+                .zip(&remaining)
+                .map(|(&((stake, _), _), &left)| stake as f64 * left as f64)
+                .sum();
+            for (index, &((stake, _), _)) in classes.iter().enumerate() {
+                if remaining[index] == 0 {
+                    continue;
+                }
+                let mut after = remaining.clone();
+                after[index] -= 1;
+                *next.entry(after).or_default() += mass * stake as f64 * remaining[index] as f64 / weight;
+            }
+        }
+        if next.len() > EXACT_DRAW_STATE_LIMIT {
+            return None;
+        }
+        states = next;
+    }
+
+    let mut distribution = vec![0.0; draws + 1];
+    for (remaining, mass) in states {
+        let drawn_faulty: usize = classes.iter()
+            .zip(&remaining)
+            .filter(|(&((_, is_faulty), _), _)| is_faulty)
+            .map(|(&(_, count), &left)| count - left)
+            .sum();
+        distribution[drawn_faulty] += mass;
+    }
+    Some(distribution)
+}
+
+/// P(base + number of successes among independent Bernoulli trials > tolerated)
+fn poisson_binomial_tail(probabilities: &[f64], base: usize, tolerated: usize) -> f64 {
+    // distribution[k] = P(exactly k successes so far)
+    let mut distribution = vec![1.0];
+    for &p in probabilities {
+        let mut next = vec![0.0; distribution.len() + 1];
+        for (k, &mass) in distribution.iter().enumerate() {
+            next[k] += mass * (1.0 - p);
+            next[k + 1] += mass * p;
+        }
+        distribution = next;
+    }
+    distribution.iter()
+        .enumerate()
+        .filter(|(k, _)| base + k > tolerated)
+        .map(|(_, mass)| mass)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn analysis_state(sampling: RelaySampling) -> AlpenglowState {
+        let nodes: Vec<NodeId> = (0..10).collect();
+        let stakes: HashMap<NodeId, StakeAmount> = nodes.iter().map(|&i| (i, 1000 + i as StakeAmount * 100)).collect();
+        let mut state = AlpenglowState::new(nodes, stakes);
+        state.relay_sampling = sampling;
+        state
+    }
+
+    #[test]
+    fn test_binomial_tail_matches_closed_form() {
+        // P(X > 1) for X ~ Binomial(3, 0.5) = 4/8
+        let tail = poisson_binomial_tail(&[0.5, 0.5, 0.5], 0, 1);
+        assert!((tail - 0.5).abs() < 1e-12);
+
+        // Fixed failures count towards the tail
+        assert_eq!(poisson_binomial_tail(&[], 2, 1), 1.0);
+        assert_eq!(poisson_binomial_tail(&[], 1, 1), 0.0);
+    }
+
+    #[test]
+    fn test_no_faulty_relays_never_fails() {
+        let state = analysis_state(RelaySampling::PartitionSampling);
+        let erasure_block = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 0.5);
+
+        let analysis = state.analyze_rotor_failure(&HashSet::new(), &erasure_block, 200);
+        assert_eq!(analysis.exact_probability, Some(0.0));
+        assert_eq!(analysis.monte_carlo_probability, 0.0);
+        assert_eq!(analysis.relay_count, 15);
+        assert_eq!(analysis.required_chunks, 10);
+    }
+
+    #[test]
+    fn test_exact_matches_monte_carlo() {
+        for sampling in [RelaySampling::WithReplacement, RelaySampling::WithoutReplacement, RelaySampling::PartitionSampling] {
+            let state = analysis_state(sampling.clone());
+            let erasure_block = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 0.5);
+            let faulty = state.faulty_nodes_for_fraction(0.35);
+
+            let analysis = state.analyze_rotor_failure(&faulty, &erasure_block, 4000);
+            let exact = analysis.exact_probability.unwrap();
+            assert!(exact > 0.0 && exact < 1.0, "{:?}: exact {}", sampling, exact);
+            assert!(
+                (exact - analysis.monte_carlo_probability).abs() < 0.03,
+                "{:?}: exact {} vs sampled {}", sampling, exact, analysis.monte_carlo_probability
+            );
+        }
+    }
+
+    #[test]
+    fn test_equal_stakes_without_replacement_is_hypergeometric() {
+        // 3 faulty among 10 equal nodes, 5 draws: P(X = k) = C(3, k) C(7, 5 - k) / C(10, 5)
+        let candidates: Vec<(NodeId, StakeAmount)> = (0..10).map(|node| (node, 100)).collect();
+        let distribution = faulty_draw_distribution(&candidates, &HashSet::from([0, 1, 2]), 5).unwrap();
+        let expected = [21.0, 105.0, 105.0, 21.0].map(|ways| ways / 252.0);
+        for (k, &p) in expected.iter().enumerate() {
+            assert!((distribution[k] - p).abs() < 1e-12, "P(X = {}) = {}", k, distribution[k]);
+        }
+        assert_eq!(distribution.len(), 6);
+        assert_eq!(distribution[4] + distribution[5], 0.0);
+    }
+
+    #[test]
+    fn test_more_redundancy_lowers_failure_risk() {
+        let state = analysis_state(RelaySampling::WithReplacement);
+        let faulty = state.faulty_nodes_for_fraction(0.4);
+
+        let low = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 0.5);
+        let high = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 2.0);
+        let low_risk = state.exact_rotor_failure_probability(&faulty, low.chunks.len(), low.required_chunks).unwrap();
+        let high_risk = state.exact_rotor_failure_probability(&faulty, high.chunks.len(), high.required_chunks).unwrap();
+        assert!(high_risk < low_risk);
+    }
+
+    #[test]
+    fn test_faulty_nodes_for_fraction_respects_budget() {
+        let state = analysis_state(RelaySampling::PartitionSampling);
+        let faulty = state.faulty_nodes_for_fraction(0.2);
+        let stake: StakeAmount = faulty.iter().map(|node| state.stake_distribution[node]).sum();
+        assert!(stake * 5 <= state.total_stake());
+        // 20% of 14500 is 2900: the largest validator fits, the next one no longer does
+        assert_eq!(faulty, HashSet::from([9, 0]));
+    }
+}
@@ -45,7 +45,7 @@ impl AlpenglowState {
         
         // Test compact state representation
         let compact_start = std::time::Instant::now();
-        let compact = state.to_compact_state();
+        let _compact = state.to_compact_state();
         let compact_time = compact_start.elapsed();
         
         let properties_time = properties_start.elapsed();
@@ -72,7 +72,7 @@ impl AlpenglowState {
         // Property 1: Network has expected number of nodes
         results.push(PropertyResult {
             property_name: "Node Count Consistency".to_string(),
-            satisfied: self.nodes.len() > 0,
+            satisfied: !self.nodes.is_empty(),
             description: format!("Network has {} nodes", self.nodes.len()),
         });
         
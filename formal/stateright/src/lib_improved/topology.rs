@@ -0,0 +1,309 @@
+// Geographic topology: validator regions and a region-pair round-trip time matrix
+use crate::lib_improved::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// Round-trip time between two regions, with optional jitter on each one-way latency
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RegionLink {
+    pub from: String,
+    pub to: String,
+    pub rtt_ms: u64,
+    #[serde(default)]
+    pub jitter_ms: u64, // one-way latency varies uniformly by up to this much either side
+}
+
+/// Where each validator runs and how far apart the regions are
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkTopology {
+    pub node_regions: BTreeMap<NodeId, String>,
+    pub links: Vec<RegionLink>, // symmetric; `from == to` gives the latency within a region
+    pub default_rtt_ms: u64,    // for nodes without a region and region pairs not in `links`
+}
+
+impl Default for NetworkTopology {
+    fn default() -> Self {
+        Self {
+            node_regions: BTreeMap::new(),
+            links: Vec::new(),
+            default_rtt_ms: 100,
+        }
+    }
+}
+
+/// Share of mainnet-like stake per region, and round-trip times between them
+const MAINNET_REGIONS: [(&str, f64); 5] = [
+    ("eu-central", 0.40),
+    ("us-east", 0.25),
+    ("eu-west", 0.20),
+    ("us-west", 0.10),
+    ("asia-east", 0.05),
+];
+const MAINNET_RTT_MS: [[u64; 5]; 5] = [
+    [2, 90, 10, 150, 230],
+    [90, 2, 75, 65, 160],
+    [10, 75, 2, 140, 220],
+    [150, 65, 140, 2, 110],
+    [230, 160, 220, 110, 2],
+];
+
+impl NetworkTopology {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse a square RTT matrix in milliseconds, with region names in the header row and
+    /// first column:
+    ///
+    /// ```text
+    /// region,us-east,eu-west
+    /// us-east,2,80
+    /// eu-west,80,2
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<Self, String> {
+        let mut rows = csv.lines().map(str::trim).filter(|line| !line.is_empty());
+        let header = rows.next().ok_or("empty RTT matrix")?;
+        let regions: Vec<&str> = header.split(',').skip(1).map(str::trim).collect();
+
+        let mut topology = Self::default();
+        let mut seen_rows = Vec::new();
+        for row in rows {
+            let mut cells = row.split(',').map(str::trim);
+            let from = cells.next().unwrap_or_default();
+            if !regions.contains(&from) {
+                return Err(format!("row region {} is not in the header", from));
+            }
+            if seen_rows.contains(&from) {
+                return Err(format!("region {} has more than one row", from));
+            }
+            seen_rows.push(from);
+            let rtts: Vec<&str> = cells.collect();
+            if rtts.len() != regions.len() {
+                return Err(format!("row {} has {} entries, expected {}", from, rtts.len(), regions.len()));
+            }
+            for (to, rtt) in regions.iter().zip(rtts) {
+                let rtt_ms = rtt.parse().map_err(|_| format!("invalid RTT {} for {} -> {}", rtt, from, to))?;
+                match topology.region_link(from, to) {
+                    Some(link) if link.rtt_ms != rtt_ms => {
+                        return Err(format!("asymmetric RTT between {} and {}: {} and {}", from, to, link.rtt_ms, rtt_ms));
+                    }
+                    Some(_) => {}
+                    None => topology.links.push(RegionLink { from: from.to_string(), to: to.to_string(), rtt_ms, jitter_ms: 0 }),
+                }
+            }
+        }
+        if let Some(missing) = regions.iter().find(|region| !seen_rows.contains(region)) {
+            return Err(format!("header region {} has no row", missing));
+        }
+        Ok(topology)
+    }
+
+    /// Stake concentrated in a few regions, roughly as on Solana mainnet; the largest
+    /// validators go first to the region furthest below its share
+    pub fn mainnet_like(stake_distribution: &HashMap<NodeId, StakeAmount>) -> Self {
+        let mut topology = Self::default();
+        for (i, (from, _)) in MAINNET_REGIONS.iter().enumerate() {
+            for (j, (to, _)) in MAINNET_REGIONS.iter().enumerate().skip(i) {
+                let rtt_ms = MAINNET_RTT_MS[i][j];
+                topology.links.push(RegionLink {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    rtt_ms,
+                    jitter_ms: rtt_ms / 20,
+                });
+            }
+        }
+
+        let total: StakeAmount = stake_distribution.values().sum();
+        let mut by_stake: Vec<(NodeId, StakeAmount)> = stake_distribution.iter().map(|(&node, &stake)| (node, stake)).collect();
+        by_stake.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let mut assigned = [0 as StakeAmount; MAINNET_REGIONS.len()];
+        for (node, stake) in by_stake {
+            let deficit = |region: usize| MAINNET_REGIONS[region].1 * total as f64 - assigned[region] as f64;
+            let region = (0..MAINNET_REGIONS.len())
+                .max_by(|&a, &b| deficit(a).total_cmp(&deficit(b)).then(b.cmp(&a)))
+                .unwrap_or(0);
+            assigned[region] += stake;
+            topology.assign(node, MAINNET_REGIONS[region].0);
+        }
+        topology
+    }
+
+    pub fn assign(&mut self, node: NodeId, region: &str) {
+        self.node_regions.insert(node, region.to_string());
+    }
+
+    /// Jitter for a region pair, adding the pair at the default RTT if it has no entry yet
+    pub fn set_jitter(&mut self, a: &str, b: &str, jitter_ms: u64) {
+        match self.links.iter_mut().find(|link| link.connects(a, b)) {
+            Some(link) => link.jitter_ms = jitter_ms,
+            None => self.links.push(RegionLink {
+                from: a.to_string(),
+                to: b.to_string(),
+                rtt_ms: self.default_rtt_ms,
+                jitter_ms,
+            }),
+        }
+    }
+
+    pub fn region_link(&self, a: &str, b: &str) -> Option<&RegionLink> {
+        self.links.iter().find(|link| link.connects(a, b))
+    }
+
+    /// Mean one-way latency and jitter between two validators
+    pub fn link_latency(&self, from: NodeId, to: NodeId) -> (u64, u64) {
+        let link = self.node_regions.get(&from)
+            .zip(self.node_regions.get(&to))
+            .and_then(|(a, b)| self.region_link(a, b));
+        match link {
+            Some(link) => (link.rtt_ms / 2, link.jitter_ms),
+            None => (self.default_rtt_ms / 2, 0),
+        }
+    }
+
+    /// Lowest and highest one-way latency between two validators
+    pub fn latency_range(&self, from: NodeId, to: NodeId) -> (u64, u64) {
+        let (mean, jitter) = self.link_latency(from, to);
+        (mean.saturating_sub(jitter).max(1), (mean + jitter).max(1))
+    }
+}
+
+impl RegionLink {
+    fn connects(&self, a: &str, b: &str) -> bool {
+        (self.from == a && self.to == b) || (self.from == b && self.to == a)
+    }
+}
+
+impl AlpenglowState {
+    /// Time from the leader sending its block until `observer` holds fast-path votes from
+    /// 80% of the stake, at mean latencies
+    pub fn fast_path_latency(&self, topology: &NetworkTopology, leader: NodeId, observer: NodeId) -> Timestamp {
+        let one_way = |from: NodeId, to: NodeId| if from == to { 0 } else { topology.link_latency(from, to).0 };
+        let mut arrivals: Vec<(Timestamp, StakeAmount)> = self.stake_distribution.iter()
+            .map(|(&voter, &stake)| (one_way(leader, voter) + one_way(voter, observer), stake))
+            .collect();
+        arrivals.sort();
+
+        let quorum = self.fast_quorum_stake();
+        let mut collected = 0;
+        for (arrival, stake) in arrivals {
+            collected += stake;
+            if collected >= quorum {
+                return arrival;
+            }
+        }
+        0
+    }
+
+    /// Stake-weighted share of (leader, observer) pairs whose fast certificate forms within `bound`
+    pub fn fast_path_success_rate(&self, topology: &NetworkTopology, bound: Timestamp) -> f64 {
+        let total = self.total_stake() as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        let mut success = 0.0;
+        for (&leader, &leader_stake) in &self.stake_distribution {
+            for (&observer, &observer_stake) in &self.stake_distribution {
+                if self.fast_path_latency(topology, leader, observer) <= bound {
+                    success += leader_stake as f64 * observer_stake as f64;
+                }
+            }
+        }
+        success / (total * total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MATRIX: &str = "region,us-east,eu-west,asia\n\
+                          us-east,2,80,160\n\
+                          eu-west,80,2,220\n\
+                          asia,160,220,2\n";
+
+    fn geo_state(stakes: &[(NodeId, StakeAmount)]) -> AlpenglowState {
+        let nodes = stakes.iter().map(|&(node, _)| node).collect();
+        AlpenglowState::new(nodes, stakes.iter().copied().collect())
+    }
+
+    #[test]
+    fn test_csv_matrix_is_symmetric() {
+        let mut topology = NetworkTopology::from_csv(MATRIX).unwrap();
+        assert_eq!(topology.links.len(), 6);
+        topology.assign(0, "us-east");
+        topology.assign(1, "asia");
+        topology.assign(2, "us-east");
+
+        assert_eq!(topology.link_latency(0, 1), (80, 0));
+        assert_eq!(topology.link_latency(1, 0), (80, 0));
+        assert_eq!(topology.link_latency(0, 2), (1, 0));
+        // Unassigned nodes fall back to the default
+        assert_eq!(topology.link_latency(0, 9), (50, 0));
+
+        topology.set_jitter("asia", "us-east", 10);
+        assert_eq!(topology.latency_range(0, 1), (70, 90));
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        assert!(NetworkTopology::from_csv("").is_err());
+        assert!(NetworkTopology::from_csv("region,a,b\na,1\n").is_err());
+        assert!(NetworkTopology::from_csv("region,a\nb,1\n").is_err());
+        assert!(NetworkTopology::from_csv("region,a\na,fast\n").is_err());
+        assert!(NetworkTopology::from_csv("region,a\na,1\na,1\n").is_err());
+
+        // Both directions of a pair must agree, and every header region needs a row
+        let asymmetric = NetworkTopology::from_csv("region,a,b\na,1,80\nb,90,1\n");
+        assert_eq!(asymmetric.unwrap_err(), "asymmetric RTT between b and a: 80 and 90");
+        let missing = NetworkTopology::from_csv("region,a,b\na,1,80\n");
+        assert_eq!(missing.unwrap_err(), "header region b has no row");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let topology = NetworkTopology::mainnet_like(&HashMap::from([(0, 10), (1, 20)]));
+        let json = serde_json::to_string(&topology).unwrap();
+        assert_eq!(NetworkTopology::from_json(&json).unwrap(), topology);
+
+        let minimal = NetworkTopology::from_json(r#"{ "node_regions": { "3": "eu" } }"#).unwrap();
+        assert_eq!(minimal.default_rtt_ms, 100);
+        assert_eq!(minimal.node_regions[&3], "eu");
+    }
+
+    #[test]
+    fn test_mainnet_like_concentrates_stake() {
+        let state = geo_state(&(0..20).map(|node| (node, 100)).collect::<Vec<_>>());
+        let topology = NetworkTopology::mainnet_like(&state.stake_distribution);
+
+        let mut region_stake: HashMap<&str, StakeAmount> = HashMap::new();
+        for (node, region) in &topology.node_regions {
+            *region_stake.entry(region.as_str()).or_default() += state.stake_distribution[node];
+        }
+        assert_eq!(region_stake["eu-central"], 800);
+        assert_eq!(region_stake["us-east"], 500);
+        assert_eq!(region_stake["asia-east"], 100);
+    }
+
+    #[test]
+    fn test_geography_limits_fast_path() {
+        // 70% of stake in one region: a fast quorum always needs a far-away validator
+        let state = geo_state(&[(0, 70), (1, 20), (2, 10)]);
+        let mut topology = NetworkTopology::from_csv(MATRIX).unwrap();
+        topology.assign(0, "us-east");
+        topology.assign(1, "eu-west");
+        topology.assign(2, "asia");
+
+        // Leader and observer in us-east: votes from eu-west arrive after 40 + 40 ms
+        assert_eq!(state.fast_path_latency(&topology, 0, 0), 80);
+        assert_eq!(state.fast_path_success_rate(&topology, 1000), 1.0);
+        let tight = state.fast_path_success_rate(&topology, 100);
+        assert!(tight > 0.0 && tight < 1.0);
+
+        // Co-locating the validators makes the fast path succeed everywhere
+        for node in 0..3 {
+            topology.assign(node, "us-east");
+        }
+        assert_eq!(state.fast_path_success_rate(&topology, 10), 1.0);
+    }
+}
@@ -18,11 +18,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("  benchmark             - Run performance benchmarks");
         println!("  explorer              - Start interactive state explorer");
         println!("  demo                  - Run comprehensive demo");
+        println!("  rotor-risk [validators] [adversarial_%] [redundancy] [trials]");
+        println!("                        - Rotor reconstruction-failure probability");
+        println!("  adversary-search [validators] [byzantine_%] [slots]");
+        println!("                        - Shortest Byzantine trace breaking notarization safety");
+        println!("                          (exhaustive to about 9 validators; larger runs stop at a state bound)");
+        println!("  attack-report [validators] [coalition_%] [slots]");
+        println!("                        - What a coalition preventing fast certificates achieves per slot");
+        println!("  resilience-sweep [validators] [step_%]");
+        println!("                        - Byzantine and crashed stake at which each guarantee fails");
         println!();
         println!("Examples:");
         println!("  {} verify 32          - Verify with 32 validators", args[0]);
         println!("  {} test               - Run all 77 tests", args[0]);
         println!("  {} demo               - Full demonstration", args[0]);
+        println!("  {} rotor-risk 16 20 0.5 - 20% adversarial stake, 50% extra chunks", args[0]);
+        println!("  {} adversary-search 5 20 - Worst case for 20% Byzantine stake", args[0]);
+        println!("  {} attack-report 10 30 3 - A 30% coalition attacking three slots", args[0]);
+        println!("  {} resilience-sweep 5 10 - Sweep 0-40% faulty stake in 10% steps", args[0]);
         println!();
         println!("🔍 For advanced features, use the CLI tools:");
         println!("  cargo run --bin alpenglow-cli --help");
@@ -64,6 +77,42 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("===================================");
             run_complete_demo()?;
         }
+        "rotor-risk" => {
+            let network_size = args.get(2).and_then(|a| a.parse().ok()).unwrap_or(16);
+            let adversarial_percent = args.get(3).and_then(|a| a.parse().ok()).unwrap_or(20.0);
+            let redundancy = args.get(4).and_then(|a| a.parse().ok()).unwrap_or(0.5);
+            let trials = args.get(5).and_then(|a| a.parse().ok()).unwrap_or(10_000);
+            
+            println!("🧮 Rotor Reconstruction-Failure Analysis");
+            println!("========================================");
+            run_rotor_risk_analysis(network_size, adversarial_percent, redundancy, trials)?;
+        }
+        "adversary-search" => {
+            let network_size = args.get(2).and_then(|a| a.parse().ok()).unwrap_or(5);
+            let byzantine_percent = args.get(3).and_then(|a| a.parse().ok()).unwrap_or(20.0);
+            let slots = args.get(4).and_then(|a| a.parse().ok()).unwrap_or(1);
+            
+            println!("🕵️  Worst-Case Adversary Search");
+            println!("==============================");
+            run_adversary_search(network_size, byzantine_percent, slots)?;
+        }
+        "attack-report" => {
+            let network_size = args.get(2).and_then(|a| a.parse().ok()).unwrap_or(10);
+            let coalition_percent = args.get(3).and_then(|a| a.parse().ok()).unwrap_or(30.0);
+            let slots = args.get(4).and_then(|a| a.parse().ok()).unwrap_or(3);
+            
+            println!("⚔️  Coordinated Attack Report");
+            println!("============================");
+            run_attack_report(network_size, coalition_percent, slots)?;
+        }
+        "resilience-sweep" => {
+            let network_size = args.get(2).and_then(|a| a.parse().ok()).unwrap_or(5);
+            let step_percent = args.get(3).and_then(|a| a.parse().ok()).unwrap_or(10.0);
+            
+            println!("📉 Resilience Threshold Sweep");
+            println!("============================");
+            run_resilience_sweep(network_size, step_percent)?;
+        }
         _ => {
             println!("❌ Unknown command: {}", args[1]);
             println!("Use --help for available commands.");
@@ -85,7 +134,7 @@ fn run_comprehensive_verification_demo(network_size: usize) -> Result<(), Box<dy
         .map(|(i, &node)| (node, 1000 + (i * 100) as StakeAmount))
         .collect();
     
-    let state = AlpenglowState::new(nodes, stakes);
+    let _state = AlpenglowState::new(nodes, stakes);
     
     println!("✅ State initialized successfully");
     println!();
@@ -247,6 +296,264 @@ fn run_explorer_demo() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn run_rotor_risk_analysis(
+    network_size: usize,
+    adversarial_percent: f64,
+    redundancy: f64,
+    trials: u32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::HashMap;
+    
+    let nodes: Vec<NodeId> = (0..network_size).map(|i| i as NodeId).collect();
+    let stakes: HashMap<NodeId, StakeAmount> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, &node)| (node, 1000 + (i * 100) as StakeAmount))
+        .collect();
+    
+    let mut state = AlpenglowState::new(nodes, stakes);
+    let faulty = state.faulty_nodes_for_fraction(adversarial_percent / 100.0);
+    let erasure_block = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, redundancy);
+    
+    println!("Network size: {} validators", network_size);
+    println!("Faulty relays: {:?}", {
+        let mut faulty: Vec<_> = faulty.iter().collect();
+        faulty.sort();
+        faulty
+    });
+    println!();
+    
+    for sampling in [
+        RelaySampling::WithReplacement,
+        RelaySampling::WithoutReplacement,
+        RelaySampling::PartitionSampling,
+    ] {
+        state.relay_sampling = sampling;
+        let analysis = state.analyze_rotor_failure(&faulty, &erasure_block, trials);
+        
+        println!("📦 {:?}", analysis.sampling);
+        println!("   • Relays (Γ): {}, required chunks (γ): {}", analysis.relay_count, analysis.required_chunks);
+        println!("   • Faulty stake: {:.2}%", analysis.faulty_stake_fraction * 100.0);
+        match analysis.exact_probability {
+            Some(p) => println!("   • Exact failure probability: {:.6e}", p),
+            None => println!("   • Exact failure probability: n/a"),
+        }
+        println!(
+            "   • Monte Carlo ({} trials): {:.6e}",
+            analysis.monte_carlo_trials, analysis.monte_carlo_probability
+        );
+        println!();
+    }
+    
+    Ok(())
+}
+
+fn run_adversary_search(
+    network_size: usize,
+    byzantine_percent: f64,
+    slots: Slot,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::HashMap;
+    
+    let nodes: Vec<NodeId> = (0..network_size).map(|i| i as NodeId).collect();
+    let stakes: HashMap<NodeId, StakeAmount> = nodes.iter().map(|&node| (node, 100)).collect();
+    let state = AlpenglowState::new(nodes, stakes);
+    let search = AdversarySearch::new(&state, byzantine_percent / 100.0, slots, vec![1, 2]);
+    
+    println!("Network size: {} validators, {} slot(s)", network_size, slots);
+    println!("Byzantine nodes: {:?} ({:.2}% of stake)", search.byzantine, search.byzantine_stake_fraction() * 100.0);
+    println!();
+    
+    let outcome = search.search();
+    match outcome.violation {
+        Some(report) => {
+            println!("❌ {} violated after {} steps:", report.property, report.trace.len());
+            for (step, action) in report.trace.iter().enumerate() {
+                println!("   {:>3}. {:?}", step + 1, action);
+            }
+        }
+        None if outcome.exhaustive => println!(
+            "✅ No Byzantine behavior within the budget notarizes conflicting blocks ({} states)",
+            outcome.states,
+        ),
+        None => println!(
+            "❔ No trace within the first {} states, but conflicting notarization is {}",
+            outcome.states,
+            if search.conflict_reachable() { "reachable" } else { "unreachable" },
+        ),
+    }
+    
+    Ok(())
+}
+
+fn run_attack_report(
+    network_size: usize,
+    coalition_percent: f64,
+    slots: Slot,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use stateright::Model;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    
+    let nodes: Vec<NodeId> = (0..network_size).map(|i| i as NodeId).collect();
+    let stakes: HashMap<NodeId, StakeAmount> = nodes.iter().map(|&node| (node, 100)).collect();
+    let mut state = AlpenglowState::new(nodes, stakes);
+    let analytics = Arc::new(Mutex::new(RunAnalytics::default()));
+    state.metrics = MetricsHandle::shared(analytics.clone());
+    
+    let mut members: Vec<NodeId> = state.faulty_nodes_for_fraction(coalition_percent / 100.0).into_iter().collect();
+    members.sort();
+    println!("Network size: {} validators, {} slot(s)", network_size, slots);
+    println!("Coalition: {:?}", members);
+    println!();
+    if members.is_empty() {
+        println!("✅ No coalition fits in {}% of the stake", coalition_percent);
+        return Ok(());
+    }
+    
+    // The coalition withholds its votes to keep slots off the fast path
+    for &node in &members {
+        state.status.insert(node, NodeStatus::Byzantine(ByzantineStrategy::WithholdVotes));
+    }
+    let step = |state: AlpenglowState, action| state.next_state(&state, action).unwrap_or(state);
+    state = step(state, AlpenglowAction::FormCoalition {
+        members,
+        strategy: CoalitionAttackType::CertificateManipulation {
+            target_path: VotePath::Fast,
+            manipulation_type: CertManipulationType::PreventCertification,
+        },
+    });
+    
+    // Each slot is attacked, then honest nodes try the fast path, the slow path and finally skip it
+    for slot in 1..=slots {
+        state = step(state, AlpenglowAction::CoordinateAttack { coalition_index: 0, target_slot: slot });
+        let block = slot as BlockId;
+        for path in [VotePath::Fast, VotePath::Slow] {
+            for node in state.live_honest_nodes() {
+                state = step(state, AlpenglowAction::Vote { node, slot, block, path: path.clone() });
+            }
+            state = step(state, AlpenglowAction::Certify { slot, path });
+            if state.certificates.contains_key(&slot) {
+                break;
+            }
+        }
+        if !state.certificates.contains_key(&slot) {
+            for node in state.live_honest_nodes() {
+                let threshold = state.timeouts.get(&node).and_then(|timeouts| timeouts.get(&slot)).map_or(0, |info| info.threshold);
+                for _ in 0..threshold {
+                    state = step(state, AlpenglowAction::Timeout { node, slot });
+                }
+            }
+            state = step(state, AlpenglowAction::SkipCert { slot });
+        }
+        let outcome = match (state.certificates.get(&slot), state.skip_certs.contains_key(&slot)) {
+            (Some(cert), _) => format!("{:?} certificate", cert.path),
+            (None, true) => "skipped".to_string(),
+            (None, false) => "unresolved".to_string(),
+        };
+        println!("   slot {}: {}", slot, outcome);
+    }
+    println!();
+    
+    for report in state.attack_reports() {
+        println!(
+            "📋 Coalition {} {:?} ({:.1}% of stake): {:?}, {} adaptation(s)",
+            report.coalition,
+            report.members,
+            report.stake_fraction * 100.0,
+            report.phase,
+            report.adaptations,
+        );
+        println!(
+            "   • Slots disrupted: {}, fast certificates prevented: {}, timeouts caused: {}, economic damage: {}",
+            report.metrics.slots_disrupted,
+            report.metrics.certificates_prevented,
+            report.metrics.timeouts_caused,
+            report.metrics.economic_damage,
+        );
+    }
+    let network = analytics.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).attack_metrics.clone();
+    println!("🌐 Network-wide: {} slot(s) disrupted, {} fast certificate(s) prevented", network.slots_disrupted, network.certificates_prevented);
+    
+    Ok(())
+}
+
+fn run_resilience_sweep(
+    network_size: usize,
+    step_percent: f64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::HashMap;
+    
+    let nodes: Vec<NodeId> = (0..network_size).map(|i| i as NodeId).collect();
+    let stakes: HashMap<NodeId, StakeAmount> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, &node)| (node, 1000 + (i * 100) as StakeAmount))
+        .collect();
+    let state = AlpenglowState::new(nodes, stakes);
+    let percent = |stake: StakeAmount| stake as f64 * 100.0 / state.total_stake() as f64;
+    
+    println!("Network size: {} validators, {}% steps", network_size, step_percent);
+    println!(
+        "Model thresholds: Byzantine {:.1}%, crash {:.1}%, fast quorum {:.1}%, slow quorum {:.1}%",
+        percent(state.byzantine_threshold_stake()),
+        percent(state.crash_threshold_stake()),
+        percent(state.fast_quorum_stake()),
+        percent(state.slow_quorum_stake()),
+    );
+    println!();
+    
+    for fault in [SweepFault::Byzantine, SweepFault::Crash] {
+        let sweep = ResilienceSweep::new(state.clone(), fault, step_percent / 100.0);
+        let points = sweep.run();
+        let mark = |holds: bool| if holds { "✅" } else { "❌" };
+        
+        println!("📊 {:?} stake (model threshold {:.1}%)", fault, sweep.threshold_fraction() * 100.0);
+        println!("   target   faulty   within   safety   slow_cert   skip_cert   fast_cert   violated properties");
+        for point in &points {
+            let violated: Vec<&str> = point.violated_properties.iter().copied().collect();
+            println!(
+                "   {:>5.1}%   {:>5.1}%     {}       {}        {}          {}          {}       {}",
+                point.requested_fraction * 100.0,
+                point.fault_fraction * 100.0,
+                mark(point.within_thresholds),
+                mark(point.no_conflicting_notarization),
+                mark(point.slow_certificate),
+                mark(point.skip_certificate),
+                mark(point.fast_certificate),
+                if violated.is_empty() { "-".to_string() } else { violated.join(", ") },
+            );
+        }
+        
+        let first_failures = [
+            ("stake_weighted_safety", ResilienceSweep::first_failure(&points, |point| point.no_conflicting_notarization)),
+            ("slow certificate", ResilienceSweep::first_failure(&points, |point| point.slow_certificate)),
+            ("skip certificate", ResilienceSweep::first_failure(&points, |point| point.skip_certificate)),
+            ("fast certificate", ResilienceSweep::first_failure(&points, |point| point.fast_certificate)),
+        ];
+        for (name, failure) in first_failures {
+            match failure {
+                Some(point) => println!(
+                    "   • {} first fails at {:.1}% faulty stake ({} the model's thresholds)",
+                    name,
+                    point.fault_fraction * 100.0,
+                    if point.within_thresholds { "within" } else { "beyond" },
+                ),
+                None => println!("   • {} holds through {:.0}%", name, sweep.max_fraction * 100.0),
+            }
+        }
+        for property in SWEPT_PROPERTIES {
+            match ResilienceSweep::first_failure(&points, |point| !point.violated_properties.contains(property)) {
+                Some(point) => println!("   • property {} first violated at {:.1}% faulty stake", property, point.fault_fraction * 100.0),
+                None => println!("   • property {} holds through {:.0}%", property, sweep.max_fraction * 100.0),
+            }
+        }
+        println!();
+    }
+    
+    Ok(())
+}
+
 fn run_complete_demo() -> Result<(), Box<dyn std::error::Error>> {
     println!("Running complete Alpenglow demonstration...");
     println!();
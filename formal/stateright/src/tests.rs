@@ -26,7 +26,7 @@ fn create_test_model() -> AlpenglowState {
         model.actions(&model, &mut actions);
         
         // Should have many possible actions
-        assert!(actions.len() > 0);
+        assert!(!actions.is_empty());
         println!("Basic consensus: {} initial actions available", actions.len());
     }
 
@@ -36,7 +36,7 @@ fn create_test_model() -> AlpenglowState {
         
         // Test properties exist and are well-formed
         let properties = model.properties();
-        assert!(properties.len() > 0, "Model should have properties defined");
+        assert!(!properties.is_empty(), "Model should have properties defined");
         
         // Test one specific safety property on a small state
         let _state = model.clone();
@@ -63,7 +63,7 @@ fn create_test_model() -> AlpenglowState {
             .filter(|action| matches!(action, AlpenglowAction::ByzantineVote { .. }))
             .collect();
             
-        assert!(byzantine_actions.len() > 0, "Byzantine nodes should have Byzantine actions available");
+        assert!(!byzantine_actions.is_empty(), "Byzantine nodes should have Byzantine actions available");
         println!("Byzantine resilience: {} Byzantine actions available", byzantine_actions.len());
     }
 
@@ -239,7 +239,7 @@ fn create_test_model() -> AlpenglowState {
         
         // Test that model defines properties correctly
         let properties = model.properties();
-        assert!(properties.len() > 0, "Model should define properties");
+        assert!(!properties.is_empty(), "Model should define properties");
     }
 
     #[tokio::test]
@@ -249,7 +249,7 @@ fn create_test_model() -> AlpenglowState {
         // Test async functionality without full model checking
         let handle = tokio::spawn(async move {
             let properties = model.properties();
-            assert!(properties.len() > 0);
+            assert!(!properties.is_empty());
             println!("Async test: {} properties defined", properties.len());
         });
         
@@ -1,5 +1,6 @@
 use crate::lib_improved::*;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 #[test]
 fn test_rotor_erasure_coding_creation() {
@@ -38,7 +39,7 @@ fn test_rotor_stake_weighted_relay_selection() {
     let block = Block { id: 1, parent: 0 };
     let erasure_block = state.create_erasure_coded_block(block, 1.0);
     
-    let relay_nodes = state.select_relay_nodes(1, &erasure_block);
+    let relay_nodes = state.select_relay_nodes(1, 0, &erasure_block);
     
     assert!(!relay_nodes.is_empty());
     
@@ -64,6 +65,77 @@ fn test_rotor_stake_weighted_relay_selection() {
     }
 }
 
+#[test]
+fn test_rotor_relay_sampling_is_deterministic() {
+    let nodes = vec![0, 1, 2, 3];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1500), (2, 2000), (3, 500)]);
+    
+    let state = AlpenglowState::new(nodes, stake_dist);
+    
+    for sampling in [RelaySampling::WithReplacement, RelaySampling::WithoutReplacement, RelaySampling::PartitionSampling] {
+        let mut state = state.clone();
+        state.relay_sampling = sampling;
+        
+        // Same (slot, leader) always yields the same relays
+        assert_eq!(state.sample_relays(3, 1, 20), state.sample_relays(3, 1, 20));
+        assert_eq!(state.sample_relays(3, 1, 20).len(), 20);
+        
+        // Different slots are seeded independently
+        let varies = (1..10).any(|slot| state.sample_relays(slot, 1, 20) != state.sample_relays(3, 1, 20));
+        assert!(varies);
+    }
+}
+
+#[test]
+fn test_rotor_relay_sampling_without_replacement() {
+    let nodes = vec![0, 1, 2, 3, 4];
+    let stake_dist = HashMap::from([(0, 5000), (1, 100), (2, 100), (3, 100), (4, 100)]);
+    
+    let mut state = AlpenglowState::new(nodes, stake_dist);
+    state.relay_sampling = RelaySampling::WithoutReplacement;
+    
+    // No relay repeats until every node has been used once
+    for slot in 1..50 {
+        let relays = state.sample_relays(slot, 0, 5);
+        let distinct: std::collections::HashSet<_> = relays.iter().collect();
+        assert_eq!(distinct.len(), 5);
+    }
+}
+
+#[test]
+fn test_rotor_relay_frequency_matches_stake_share() {
+    let nodes = vec![0, 1, 2, 3];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1500), (2, 2000), (3, 500)]);
+    
+    let state = AlpenglowState::new(nodes.clone(), stake_dist.clone());
+    let total_stake = state.total_stake() as f64;
+    
+    for sampling in [RelaySampling::WithReplacement, RelaySampling::PartitionSampling] {
+        let mut state = state.clone();
+        state.relay_sampling = sampling.clone();
+        
+        let mut counts: HashMap<NodeId, u64> = HashMap::new();
+        let mut draws = 0u64;
+        for slot in 1..=500 {
+            for leader in &nodes {
+                for relay in state.sample_relays(slot, *leader, 15) {
+                    *counts.entry(relay).or_insert(0) += 1;
+                    draws += 1;
+                }
+            }
+        }
+        
+        for node in &nodes {
+            let frequency = *counts.get(node).unwrap_or(&0) as f64 / draws as f64;
+            let share = stake_dist[node] as f64 / total_stake;
+            assert!(
+                (frequency - share).abs() < 0.02,
+                "{:?}: node {} selected {:.3} vs stake share {:.3}", sampling, node, frequency, share
+            );
+        }
+    }
+}
+
 #[test]
 fn test_rotor_chunk_reconstruction() {
     let nodes = vec![0, 1, 2];
@@ -75,21 +147,95 @@ fn test_rotor_chunk_reconstruction() {
     let erasure_block = state.create_erasure_coded_block(block, 0.5);
     
     // Initially, block cannot be reconstructed (no chunks available)
-    assert!(!state.can_reconstruct_block(1));
+    assert!(!state.can_reconstruct_block(0, 1));
     
     // Add the erasure block and set up chunks
     state.erasure_coded_blocks.insert(1, erasure_block.clone());
     
-    // Simulate having enough chunks available from different nodes
+    // Spread enough chunks across nodes: collectively enough, individually not
     for i in 0..erasure_block.required_chunks {
         state.chunk_availability
             .entry((1, i as u32))
-            .or_insert_with(std::collections::HashSet::new)
+            .or_default()
             .insert(i as u32 % 3); // Distribute across nodes
     }
     
-    // Now block should be reconstructable
-    assert!(state.can_reconstruct_block(1));
+    for node in 0..3 {
+        assert!(!state.can_reconstruct_block(node, 1));
+    }
+    
+    // Node 0 receives every chunk it was missing
+    for i in 0..erasure_block.required_chunks {
+        state.chunk_availability.get_mut(&(1, i as u32)).unwrap().insert(0);
+    }
+    
+    // Now block should be reconstructable by node 0 only
+    assert!(state.can_reconstruct_block(0, 1));
+    assert!(!state.can_reconstruct_block(1, 1));
+}
+
+#[test]
+fn test_rotor_reconstruct_action_is_per_node() {
+    let nodes = vec![0, 1, 2];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1000), (2, 1000)]);
+    
+    let mut state = AlpenglowState::new(nodes, stake_dist);
+    let model = AlpenglowModel::new();
+    
+    let block = Block { id: 1, parent: 0 };
+    let erasure_block = state.create_erasure_coded_block(block, 0.5);
+    state.erasure_coded_blocks.insert(1, erasure_block.clone());
+    
+    // Node 0 holds everything; node 1 holds a single chunk
+    for chunk in &erasure_block.chunks {
+        state.chunk_availability.entry((1, chunk.chunk_id)).or_default().insert(0);
+    }
+    state.chunk_availability.get_mut(&(1, 0)).unwrap().insert(1);
+    
+    // Node 0's chunks do not let node 1 reconstruct
+    let after = model.next_state(&state, AlpenglowAction::ReconstructBlock { node: 1, block_id: 1 }).unwrap();
+    assert_eq!(after.chunks_held_by(1, 1).len(), 1);
+    assert!(!after.can_reconstruct_block(1, 1));
+    
+    // A node with enough chunks recovers the whole block
+    for i in 0..erasure_block.required_chunks {
+        state.chunk_availability.get_mut(&(1, i as u32)).unwrap().insert(2);
+    }
+    let after = model.next_state(&state, AlpenglowAction::ReconstructBlock { node: 2, block_id: 1 }).unwrap();
+    assert_eq!(after.chunks_held_by(2, 1).len(), erasure_block.chunks.len());
+}
+
+#[test]
+fn test_erasure_block_availability_requires_voter_reconstruction() {
+    use stateright::Model;
+    
+    let nodes = vec![0, 1, 2];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1000), (2, 1000)]);
+    
+    let mut state = AlpenglowState::new(nodes, stake_dist);
+    let model = AlpenglowModel::new();
+    
+    let block = Block { id: 1, parent: 0 };
+    let erasure_block = state.create_erasure_coded_block(block, 0.5);
+    state.erasure_coded_blocks.insert(1, erasure_block.clone());
+    
+    // Node 0 can reconstruct, node 1 cannot
+    for chunk in &erasure_block.chunks {
+        state.chunk_availability.entry((1, chunk.chunk_id)).or_default().insert(0);
+    }
+    
+    let holds = |state: &AlpenglowState| {
+        let properties = state.properties();
+        let property = properties.iter().find(|p| p.name == "erasure_block_availability").unwrap();
+        (property.condition)(state, state)
+    };
+    
+    let state = model.next_state(&state, AlpenglowAction::Vote { node: 0, slot: 1, block: 1, path: VotePath::Fast }).unwrap();
+    assert!(holds(&state));
+    
+    // Node 1 voting for a block it never received violates availability
+    let state = model.next_state(&state, AlpenglowAction::Vote { node: 1, slot: 1, block: 1, path: VotePath::Fast }).unwrap();
+    assert!(!holds(&state));
 }
 
 #[test]
@@ -102,7 +248,7 @@ fn test_leader_rotation_deterministic() {
     // Test deterministic leader selection
     let leader_1 = state.get_leader_for_slot(1);
     let leader_2 = state.get_leader_for_slot(2);
-    let leader_3 = state.get_leader_for_slot(3);
+    let _leader_3 = state.get_leader_for_slot(3);
     
     // Leaders should be deterministic for same slot
     assert_eq!(leader_1, state.get_leader_for_slot(1));
@@ -157,12 +303,12 @@ fn test_bounded_finalization_time() {
     let slot_start_time = slot as u64 * 1000; // 1 second per slot
     let valid_finalization_time = slot_start_time + 400; // Within 500ms bound
     
-    state.finalization_times.insert(slot, valid_finalization_time);
+    state.ledger.push(FinalizedBlock { slot, block_id: 1, finalization_time: valid_finalization_time, total_stake: 3000 });
     assert!(state.check_finalization_time_bounds(slot));
     
     // Test with invalid finalization time
     let invalid_finalization_time = slot_start_time + 1200; // Exceeds min(500, 2*1000) = 500ms
-    state.finalization_times.insert(slot, invalid_finalization_time);
+    state.ledger[0].finalization_time = invalid_finalization_time;
     assert!(!state.check_finalization_time_bounds(slot));
 }
 
@@ -180,6 +326,7 @@ fn test_rotor_erasure_actions() {
     // Test PropagateErasureBlock action
     let action = AlpenglowAction::PropagateErasureBlock { 
         node: 0, 
+        slot: 1,
         erasure_block: erasure_block.clone() 
     };
     
@@ -205,6 +352,336 @@ fn test_rotor_erasure_actions() {
     assert!(chunk_state.chunk_availability.contains_key(&(1, 0)));
 }
 
+#[test]
+fn test_rotor_relay_assignments_scoped_per_block() {
+    let nodes = vec![0, 1, 2];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1000), (2, 1000)]);
+    
+    let state = AlpenglowState::new(nodes, stake_dist);
+    let model = AlpenglowModel::new();
+    
+    // Two pipelined blocks from different leaders
+    let first = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 1.0);
+    let second = state.create_erasure_coded_block(Block { id: 2, parent: 1 }, 1.0);
+    let state = model.next_state(&state, AlpenglowAction::PropagateErasureBlock {
+        node: 0, slot: 1, erasure_block: first,
+    }).unwrap();
+    let state = model.next_state(&state, AlpenglowAction::PropagateErasureBlock {
+        node: 1, slot: 2, erasure_block: second,
+    }).unwrap();
+    
+    // The second block's relay roles do not overwrite the first block's
+    let first_roles: Vec<_> = state.relay_assignments.values().filter(|a| a.block_id == 1).collect();
+    let second_roles: Vec<_> = state.relay_assignments.values().filter(|a| a.block_id == 2).collect();
+    assert!(!first_roles.is_empty());
+    assert!(!second_roles.is_empty());
+    assert!(first_roles.iter().all(|a| a.slot == 1 && a.leader == 0));
+    assert!(second_roles.iter().all(|a| a.slot == 2 && a.leader == 1));
+    
+    // Finalizing slot 1 expires only slot 1's relay roles
+    let mut state = state;
+    for node in 0..3 {
+        state = model.next_state(&state, AlpenglowAction::Vote { node, slot: 1, block: 1, path: VotePath::Fast }).unwrap();
+    }
+    let state = model.next_state(&state, AlpenglowAction::Certify { slot: 1, path: VotePath::Fast }).unwrap();
+    assert!(state.certificates.contains_key(&1));
+    assert!(state.relay_assignments.values().all(|a| a.slot == 2));
+    assert!(!state.relay_assignments.is_empty());
+}
+
+/// Deliver every due message, advancing time until the queue drains
+/// Point `state`'s metrics at a fresh `RunAnalytics` and return it
+fn install_analytics(state: &mut AlpenglowState) -> Arc<Mutex<RunAnalytics>> {
+    let analytics = Arc::new(Mutex::new(RunAnalytics::default()));
+    state.metrics = MetricsHandle::shared(analytics.clone());
+    analytics
+}
+
+fn run_until_delivered(model: &AlpenglowModel, mut state: AlpenglowState) -> AlpenglowState {
+    while !state.message_queue.pending_messages.is_empty() {
+        let due: Vec<u64> = state.message_queue.pending_messages.iter()
+            .filter(|msg| msg.scheduled_delivery_time <= state.global_time)
+            .map(|msg| msg.id)
+            .collect();
+        for message_id in due {
+            state = model.next_state(&state, AlpenglowAction::DeliverMessage { message_id }).unwrap();
+        }
+        state = model.next_state(&state, AlpenglowAction::AdvanceTime { delta: 1 }).unwrap();
+    }
+    state
+}
+
+#[test]
+fn test_rotor_two_hop_dissemination_timing() {
+    let nodes = vec![0, 1, 2, 3];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1000), (2, 1000), (3, 1000)]);
+    
+    let mut state = AlpenglowState::new(nodes.clone(), stake_dist);
+    let analytics = install_analytics(&mut state);
+    let model = AlpenglowModel::new();
+    
+    let erasure_block = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 1.0);
+    let state = model.next_state(&state, AlpenglowAction::PropagateErasureBlock {
+        node: 0, slot: 1, erasure_block,
+    }).unwrap();
+    
+    // Only the leader has the block until chunks travel through the message layer
+    assert!(state.can_reconstruct_block(0, 1));
+    assert!(!state.can_reconstruct_block(1, 1));
+    assert!(state.message_queue.pending_messages.iter()
+        .all(|msg| matches!(msg.content, MessageContent::Chunk { .. })));
+    
+    let state = run_until_delivered(&model, state);
+    
+    // Every node reconstructs after a leader->relay->all path: at least two network hops
+    let analytics = analytics.lock().unwrap();
+    let latencies = analytics.block_arrival_latencies(1);
+    assert_eq!(latencies.len(), nodes.len());
+    assert_eq!(latencies[&0], 0);
+    for node in 1..4 {
+        assert!(state.can_reconstruct_block(node, 1));
+        assert!(latencies[&node] >= 50, "node {} got the block after {}ms", node, latencies[&node]);
+    }
+    assert!(analytics.stake_weighted_arrival_latency(&state, 1, 80).unwrap() <= 2 * 50 + 100);
+    assert!(analytics.rotor_meets_delta_bounds(&state, 1));
+}
+
+#[test]
+fn test_rotor_serialization_delay_depends_on_bandwidth() {
+    let nodes = vec![0, 1, 2, 3];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1000), (2, 1000), (3, 1000)]);
+    
+    let mut state = AlpenglowState::new(nodes, stake_dist);
+    let model = AlpenglowModel::new();
+    
+    let analytics = install_analytics(&mut state);
+    
+    // 64-byte chunks over a 128 B/s leader uplink take 500ms each
+    state.network_state.node_bandwidth.insert(0, 128);
+    assert_eq!(state.serialization_delay(&state, 0, 64), 500);
+    assert_eq!(state.serialization_delay(&state, 1, 64), 1);
+    
+    let erasure_block = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 1.0);
+    let state = model.next_state(&state, AlpenglowAction::PropagateErasureBlock {
+        node: 0, slot: 1, erasure_block,
+    }).unwrap();
+    let state = run_until_delivered(&model, state);
+    
+    // A slow leader uplink pushes Rotor past the δ assumptions
+    let analytics = analytics.lock().unwrap();
+    assert!(analytics.stake_weighted_arrival_latency(&state, 1, 80).unwrap() > DELTA_80_MS);
+    assert!(!analytics.rotor_meets_delta_bounds(&state, 1));
+}
+
+#[test]
+fn test_rotor_relay_uplink_serializes_across_chunks() {
+    let nodes = vec![0, 1, 2];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1000), (2, 1000)]);
+    
+    let mut state = AlpenglowState::new(nodes, stake_dist);
+    let model = AlpenglowModel::new();
+    let erasure_block = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 1.0);
+    state.erasure_coded_blocks.insert(1, erasure_block.clone());
+    for chunk in &erasure_block.chunks[..2] {
+        state.chunk_availability.entry((1, chunk.chunk_id)).or_default().insert(1);
+    }
+    
+    // Relay 1 forwards two chunks it got separately over a slow uplink
+    state.network_state.node_bandwidth.insert(1, 128);
+    let send_time = {
+        let content = MessageContent::Chunk { chunk: erasure_block.chunks[0].clone(), from_leader: false };
+        state.serialization_delay(&state, 1, content.wire_size())
+    };
+    let mut forwarded = state.clone();
+    for chunk in &erasure_block.chunks[..2] {
+        forwarded = model.next_state(&forwarded, AlpenglowAction::PropagateChunk {
+            node: 1, chunk: chunk.clone(), target_nodes: vec![2],
+        }).unwrap();
+    }
+    let arrivals: Vec<Timestamp> = forwarded.message_queue.pending_messages.iter()
+        .map(|msg| msg.scheduled_delivery_time)
+        .collect();
+    assert_eq!(arrivals[0], 50 + send_time);
+    assert!(arrivals[1] >= arrivals[0] + send_time, "second chunk arrived at {}", arrivals[1]);
+    
+    // A throttled link serializes in its own queue, without the uplink time on top
+    state.network_state.bandwidth_limits.insert((1, 2), 128);
+    let throttled = model.next_state(&state, AlpenglowAction::PropagateChunk {
+        node: 1, chunk: erasure_block.chunks[0].clone(), target_nodes: vec![2],
+    }).unwrap();
+    assert_eq!(throttled.message_queue.pending_messages[0].scheduled_delivery_time, 50 + send_time);
+}
+
+#[test]
+fn test_rotor_partitioned_node_repairs_notarized_block() {
+    use stateright::Model;
+    use std::collections::HashSet;
+    
+    let nodes = vec![0, 1, 2, 3];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1000), (2, 1000), (3, 1000)]);
+    
+    // No packet loss, so only the partition keeps node 3 from the block
+    let mut state = AlpenglowState::new(nodes, stake_dist);
+    state.network_state.packet_loss_rate = 0.0;
+    let model = AlpenglowModel::new();
+    
+    // Node 3 is cut off while the block is disseminated
+    let state = model.next_state(&state, AlpenglowAction::NetworkPartition {
+        nodes_a: HashSet::from([0, 1, 2]),
+        nodes_b: HashSet::from([3]),
+    }).unwrap();
+    let erasure_block = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 1.0);
+    let state = model.next_state(&state, AlpenglowAction::PropagateErasureBlock {
+        node: 0, slot: 1, erasure_block,
+    }).unwrap();
+    let state = run_until_delivered(&model, state);
+    assert!(state.chunks_held_by(3, 1).is_empty());
+    
+    // Repair requests are dropped while the partition lasts
+    let blocked = model.next_state(&state, AlpenglowAction::RequestMissingChunks {
+        node: 3, block_id: 1, missing_chunks: vec![],
+    }).unwrap();
+    let blocked = run_until_delivered(&model, blocked);
+    assert!(!blocked.can_reconstruct_block(3, 1));
+    
+    // The majority side notarizes the block while node 3 is still cut off
+    let mut state = state;
+    for node in 0..3 {
+        state = model.next_state(&state, AlpenglowAction::Vote { node, slot: 1, block: 1, path: VotePath::Slow }).unwrap();
+    }
+    let partitioned = model.next_state(&state, AlpenglowAction::Certify { slot: 1, path: VotePath::Slow }).unwrap();
+    assert_eq!(partitioned.unrepaired_notarized_blocks(3), vec![1]);
+    assert!(!partitioned.network_delivers_everything());
+    assert!(!partitioned.repair_completes(3, 1));
+    
+    let properties = partitioned.properties();
+    let repairable = properties.iter().find(|p| p.name == "notarized_block_repairable").unwrap();
+    assert!((repairable.condition)(&partitioned, &partitioned));
+    
+    // Past GST the partition only delays repair traffic, so node 3 catches up
+    let mut synchronous = partitioned.clone();
+    synchronous.network_state.partial_synchrony = Some(PartialSynchrony::new(synchronous.global_time));
+    assert!(synchronous.network_delivers_everything());
+    assert!(synchronous.repair_completes(3, 1));
+    assert!((repairable.condition)(&synchronous, &synchronous));
+    
+    // Once the partition heals, the property requires node 3 to repair the block
+    let state = model.next_state(&partitioned, AlpenglowAction::HealPartition).unwrap();
+    assert!(state.network_delivers_everything());
+    assert!(state.repair_completes(3, 1));
+    assert!((repairable.condition)(&state, &state));
+    
+    // The model offers repair to the lagging node, and repair completes over the network
+    let mut actions = Vec::new();
+    state.actions(&state, &mut actions);
+    let repair = actions.into_iter()
+        .find(|a| matches!(a, AlpenglowAction::RequestMissingChunks { node: 3, block_id: 1, .. }))
+        .expect("repair should be offered for the notarized block");
+    let state = model.next_state(&state, repair).unwrap();
+    assert!(state.message_queue.pending_messages.iter()
+        .any(|msg| matches!(msg.content, MessageContent::RepairRequest { .. })));
+    
+    let state = run_until_delivered(&model, state);
+    assert!(state.can_reconstruct_block(3, 1));
+    assert!(state.unrepaired_notarized_blocks(3).is_empty());
+}
+
+/// Disseminate one block from leader 0 over five equal-stake nodes, each relaying three of 15 chunks
+fn disseminate_with_relay_attacks(attacks: &[(NodeId, RelayAttackType)]) -> (AlpenglowState, RunAnalytics) {
+    let nodes = vec![0, 1, 2, 3, 4];
+    let stake_dist = HashMap::from([(0, 1000), (1, 1000), (2, 1000), (3, 1000), (4, 1000)]);
+    
+    let mut state = AlpenglowState::new(nodes, stake_dist);
+    for (node, behavior) in attacks {
+        state.status.insert(*node, NodeStatus::Byzantine(ByzantineStrategy::RelayAttack {
+            behavior: behavior.clone(),
+        }));
+    }
+    let analytics = install_analytics(&mut state);
+    let model = AlpenglowModel::new();
+    
+    let erasure_block = state.create_erasure_coded_block(Block { id: 1, parent: 0 }, 0.5);
+    assert_eq!((erasure_block.chunks.len(), erasure_block.required_chunks), (15, 10));
+    let state = model.next_state(&state, AlpenglowAction::PropagateErasureBlock {
+        node: 0, slot: 1, erasure_block,
+    }).unwrap();
+    let state = run_until_delivered(&model, state);
+    let analytics = analytics.lock().unwrap().clone();
+    (state, analytics)
+}
+
+#[test]
+fn test_rotor_tolerates_dropping_relay() {
+    let (state, analytics) = disseminate_with_relay_attacks(&[(4, RelayAttackType::DropChunks)]);
+    
+    // Three withheld chunks leave the honest nodes with 12 of the 10 they need
+    for node in 1..4 {
+        assert_eq!(state.chunks_held_by(node, 1).len(), 12);
+        assert!(state.can_reconstruct_block(node, 1));
+    }
+    // Each of relay 4's chunks is withheld from nodes 1, 2 and 3
+    assert_eq!(analytics.attack_metrics.chunks_withheld, 9);
+}
+
+#[test]
+fn test_rotor_fails_when_dropping_relays_exceed_redundancy() {
+    let (state, analytics) = disseminate_with_relay_attacks(&[
+        (3, RelayAttackType::DropChunks),
+        (4, RelayAttackType::DropChunks),
+    ]);
+    
+    for node in 1..3 {
+        assert_eq!(state.chunks_held_by(node, 1).len(), 9);
+        assert!(!state.can_reconstruct_block(node, 1));
+    }
+    assert_eq!(analytics.attack_metrics.chunks_withheld, 2 * 3 * 3);
+}
+
+#[test]
+fn test_rotor_discards_corrupted_chunks() {
+    let (state, analytics) = disseminate_with_relay_attacks(&[(4, RelayAttackType::CorruptChunks)]);
+    
+    let relayed_by_4 = state.chunks_held_by(4, 1);
+    assert_eq!(relayed_by_4.len(), 15, "the relay itself decodes from honest chunks");
+    for node in 1..4 {
+        assert_eq!(state.chunks_held_by(node, 1).len(), 12);
+        assert!(state.can_reconstruct_block(node, 1));
+    }
+    assert_eq!(analytics.attack_metrics.chunks_corrupted, 9);
+    assert_eq!(analytics.attack_metrics.chunks_withheld, 0);
+}
+
+#[test]
+fn test_rotor_relay_forwards_to_subset() {
+    let (state, analytics) = disseminate_with_relay_attacks(&[
+        (3, RelayAttackType::ForwardToSubset { recipients: vec![1] }),
+        (4, RelayAttackType::ForwardToSubset { recipients: vec![1] }),
+    ]);
+    
+    // Node 1 is favored and decodes; node 2 misses both Byzantine relays' chunks
+    assert!(state.can_reconstruct_block(1, 1));
+    assert!(!state.can_reconstruct_block(2, 1));
+    assert_eq!(analytics.attack_metrics.chunks_withheld, 2 * 3 * 2);
+}
+
+#[test]
+fn test_rotor_delayed_relay_slows_arrival() {
+    let (_, honest) = disseminate_with_relay_attacks(&[]);
+    let (delayed_state, delayed) = disseminate_with_relay_attacks(&[
+        (3, RelayAttackType::DelayForwarding { delay_ms: 400 }),
+        (4, RelayAttackType::DelayForwarding { delay_ms: 400 }),
+    ]);
+    
+    // Honest nodes still decode, but only once the delayed chunks arrive
+    for node in 1..3 {
+        assert!(delayed_state.can_reconstruct_block(node, 1));
+        assert!(delayed.block_arrival_latencies(1)[&node] >= 400);
+        assert!(honest.block_arrival_latencies(1)[&node] < 400);
+    }
+    assert_eq!(delayed.attack_metrics.chunks_delayed, 2 * 3 * 3);
+    assert_eq!(honest.attack_metrics, AttackMetrics::default());
+}
+
 #[test]
 fn test_leader_rotation_actions() {
     let nodes = vec![0, 1, 2];
@@ -259,7 +736,7 @@ fn test_complete_alpenglow_protocol_properties() {
     for i in 0..erasure_block.required_chunks {
         state.chunk_availability
             .entry((1, i as u32))
-            .or_insert_with(std::collections::HashSet::new)
+            .or_default()
             .insert(i as u32 % 5);
     }
     
@@ -269,8 +746,8 @@ fn test_complete_alpenglow_protocol_properties() {
     }
     
     // Add valid finalization times
-    state.finalization_times.insert(1, 1400); // Within bounds
-    state.finalization_times.insert(2, 2450); // Within bounds
+    state.ledger.push(FinalizedBlock { slot: 1, block_id: 1, finalization_time: 1400, total_stake: 5000 }); // Within bounds
+    state.ledger.push(FinalizedBlock { slot: 2, block_id: 2, finalization_time: 2450, total_stake: 5000 }); // Within bounds
     
     // Test all properties
     use stateright::Model;
@@ -1,6 +1,6 @@
 use crate::*;
 use stateright::Model;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[test]
 fn test_selective_equivocation() {
@@ -159,6 +159,7 @@ fn test_coalition_coordination() {
             certificates_prevented: 0,
             timeouts_caused: 0,
             economic_damage: 0,
+            ..Default::default()
         },
         adaptation_count: 0,
     });
@@ -221,7 +222,7 @@ fn test_timing_attack() {
     
     // Vote should still be cast but with delay
     let node1_votes = &new_state.votes[&1][&1];
-    assert!(node1_votes.len() > 0, "Vote should be cast after delay");
+    assert!(!node1_votes.is_empty(), "Vote should be cast after delay");
     assert_eq!(node1_votes[0].path, VotePath::Fast, "Should target Fast path");
 }
 
@@ -234,10 +235,11 @@ fn test_stake_based_attack() {
     stake_distribution.insert(3, 100);
     
     let mut state = AlpenglowState::new(nodes, stake_distribution);
+    // Slashing 10% of a 1000 balance costs 100, inside the margin
     state.status.insert(1, NodeStatus::Byzantine(ByzantineStrategy::StakeBasedAttack {
         reserve_stake_for_critical_slots: true,
         activation_threshold: 500,
-        min_profit_margin: 50,
+        min_profit_margin: 150,
     }));
     
     let model = state.clone();
@@ -248,7 +250,7 @@ fn test_stake_based_attack() {
         strategy: ByzantineStrategy::StakeBasedAttack {
             reserve_stake_for_critical_slots: true,
             activation_threshold: 500,
-            min_profit_margin: 50,
+            min_profit_margin: 150,
         },
         slot: 3, // Critical slot (3 % 3 == 0)
     };
@@ -265,7 +267,7 @@ fn test_stake_based_attack() {
         strategy: ByzantineStrategy::StakeBasedAttack {
             reserve_stake_for_critical_slots: true,
             activation_threshold: 500,
-            min_profit_margin: 50,
+            min_profit_margin: 150,
         },
         slot: 2, // Regular slot
     };
@@ -275,6 +277,21 @@ fn test_stake_based_attack() {
     // Should be more conservative on regular slots
     let node1_regular_votes = &new_state2.votes[&1][&2];
     assert_eq!(node1_regular_votes.len(), 1, "Should be conservative on regular slots");
+    
+    // Once the expected slashing reaches the margin the attack no longer pays
+    let unprofitable_action = AlpenglowAction::ByzantineVote {
+        node: 1,
+        strategy: ByzantineStrategy::StakeBasedAttack {
+            reserve_stake_for_critical_slots: true,
+            activation_threshold: 500,
+            min_profit_margin: 100,
+        },
+        slot: 3,
+    };
+    let new_state3 = model.next_state(&state, unprofitable_action).unwrap();
+    let node1_unprofitable_votes = &new_state3.votes[&1][&3];
+    assert_eq!(node1_unprofitable_votes.len(), 1, "Should vote honestly when slashing outweighs profit");
+    assert_eq!(node1_unprofitable_votes[0].block, 0);
 }
 
 #[test]
@@ -305,7 +322,7 @@ fn test_strategy_adaptation_actions() {
     assert!(!adaptation_actions.is_empty(), "Should have strategy adaptation actions");
     
     // Test adaptation action
-    if let AlpenglowAction::AdaptStrategy { node, new_strategy, reason: _ } = &adaptation_actions[0] {
+    if let AlpenglowAction::AdaptStrategy { node, new_strategy: _, reason: _ } = &adaptation_actions[0] {
         let adapt_action = adaptation_actions[0].clone();
         let new_state = model.next_state(&state, adapt_action).unwrap();
         
@@ -314,4 +331,318 @@ fn test_strategy_adaptation_actions() {
             assert_ne!(*updated_strategy, ByzantineStrategy::Equivocation, "Strategy should change");
         }
     }
-}
\ No newline at end of file
+    
+    // Escalation equivocates only for validators with at least average stake
+    assert!(actions.iter().any(|action| matches!(
+        action,
+        AlpenglowAction::AdaptStrategy {
+            new_strategy: ByzantineStrategy::SelectiveEquivocation { min_stake_threshold, .. },
+            ..
+        } if *min_stake_threshold == state.average_stake()
+    )));
+}
+
+#[test]
+fn test_coalition_strategy_parameters() {
+    let nodes: Vec<NodeId> = (1..=10).collect();
+    let stake_distribution = nodes.iter().map(|&node| (node, 100)).collect();
+    let state = AlpenglowState::new(nodes, stake_distribution);
+    let coalition = |members: Vec<NodeId>, attack_type: CoalitionAttackType| {
+        let mut state = state.clone();
+        let strategy = ByzantineStrategy::CoalitionAttack { coalition_members: members.clone(), attack_type };
+        for &node in &members {
+            state.status.insert(node, NodeStatus::Byzantine(strategy.clone()));
+        }
+        (state, strategy)
+    };
+    
+    // Delayed certification votes for the slot `delay_slots` back
+    let (delayed, strategy) = coalition(vec![1], CoalitionAttackType::CertificateManipulation {
+        target_path: VotePath::Slow,
+        manipulation_type: CertManipulationType::DelayedCertification { delay_slots: 3 },
+    });
+    let delayed = delayed.next_state(&delayed, AlpenglowAction::ByzantineVote { node: 1, strategy, slot: 5 }).unwrap();
+    assert_eq!(delayed.votes[&1][&2].len(), 1);
+    assert!(delayed.votes[&1][&3].is_empty());
+    
+    // 10% of the stake is half the fast path's 20% slack: enough to exceed a 0.4 threshold,
+    // but not 0.5 or 0.7
+    assert_eq!(state.disruption_probability(&[1]), 0.5);
+    assert_eq!(state.disruption_probability(&[1, 2, 3]), 1.0);
+    for (threshold, votes) in [(0.4, 3), (0.5, 1), (0.7, 1)] {
+        let (targeting, strategy) = coalition(vec![1], CoalitionAttackType::StrategicTargeting {
+            high_priority_slots: vec![1],
+            disruption_threshold: threshold,
+        });
+        let targeting = targeting.next_state(&targeting, AlpenglowAction::ByzantineVote { node: 1, strategy, slot: 1 }).unwrap();
+        assert_eq!(targeting.votes[&1][&1].len(), votes, "threshold {}", threshold);
+    }
+}
+
+/// Deliver every pending message, and those they trigger, regardless of schedule
+fn deliver_all(mut state: AlpenglowState) -> AlpenglowState {
+    let model = state.clone();
+    while let Some(message) = state.message_queue.pending_messages.first() {
+        let message_id = message.id;
+        state = model.next_state(&state, AlpenglowAction::DeliverMessage { message_id }).unwrap();
+    }
+    state
+}
+
+/// Blocks that `observer` holds votes for from `voter` in `slot`
+fn blocks_seen(state: &AlpenglowState, observer: NodeId, voter: NodeId, slot: Slot) -> Vec<BlockId> {
+    let mut blocks: Vec<BlockId> = state.votes[&observer][&slot].iter()
+        .filter(|vote| vote.node == voter)
+        .map(|vote| vote.block)
+        .collect();
+    blocks.sort_unstable();
+    blocks.dedup();
+    blocks
+}
+
+#[test]
+fn test_equivocation_targets_recipient_halves() {
+    let nodes = vec![1, 2, 3, 4, 5];
+    let stake_distribution = nodes.iter().map(|&node| (node, 100)).collect();
+    let mut state = AlpenglowState::new(nodes, stake_distribution);
+    state.network_state.packet_loss_rate = 0.0;
+    state.status.insert(1, NodeStatus::Byzantine(ByzantineStrategy::Equivocation));
+    
+    let sent = state.next_state(&state, AlpenglowAction::ByzantineVote {
+        node: 1,
+        strategy: ByzantineStrategy::Equivocation,
+        slot: 1,
+    }).unwrap();
+    
+    // One vote message per recipient, with different content per half
+    assert_eq!(sent.message_queue.pending_messages.len(), 4);
+    let delivered = deliver_all(sent);
+    assert_eq!(blocks_seen(&delivered, 2, 1, 1), vec![0]);
+    assert_eq!(blocks_seen(&delivered, 3, 1, 1), vec![0]);
+    assert_eq!(blocks_seen(&delivered, 4, 1, 1), vec![1]);
+    assert_eq!(blocks_seen(&delivered, 5, 1, 1), vec![1]);
+    // The equivocator's own record holds both signed votes
+    assert_eq!(blocks_seen(&delivered, 1, 1, 1), vec![0, 1]);
+}
+
+#[test]
+fn test_split_vote_shows_each_group_one_block() {
+    let nodes: Vec<NodeId> = (1..=8).collect();
+    let stake_distribution = nodes.iter().map(|&node| (node, 100)).collect();
+    let mut state = AlpenglowState::new(nodes, stake_distribution);
+    state.network_state.packet_loss_rate = 0.0;
+    let strategy = ByzantineStrategy::CoalitionAttack {
+        coalition_members: vec![1, 2],
+        attack_type: CoalitionAttackType::SplitVote { target_blocks: vec![0, 1, 2] },
+    };
+    for node in [1, 2] {
+        state.status.insert(node, NodeStatus::Byzantine(strategy.clone()));
+    }
+    
+    let mut sent = state.clone();
+    for node in [1, 2] {
+        sent = sent.next_state(&sent, AlpenglowAction::ByzantineVote { node, strategy: strategy.clone(), slot: 1 }).unwrap();
+    }
+    let delivered = deliver_all(sent);
+    
+    // Honest nodes 3..=8 fall into three groups, each shown the same block by both members
+    for (observer, block) in [(3, 0), (4, 0), (5, 1), (6, 1), (7, 2), (8, 2)] {
+        assert_eq!(blocks_seen(&delivered, observer, 1, 1), vec![block], "node {}", observer);
+        assert_eq!(blocks_seen(&delivered, observer, 2, 1), vec![block], "node {}", observer);
+    }
+}
+
+#[test]
+fn test_conflicting_certificates_in_recipient_views() {
+    let nodes = vec![1, 2, 3, 4];
+    let stake_distribution = nodes.iter().map(|&node| (node, 100)).collect();
+    let mut state = AlpenglowState::new(nodes, stake_distribution);
+    state.network_state.packet_loss_rate = 0.0;
+    let strategy = ByzantineStrategy::CoalitionAttack {
+        coalition_members: vec![1, 2],
+        attack_type: CoalitionAttackType::CertificateManipulation {
+            target_path: VotePath::Slow,
+            manipulation_type: CertManipulationType::ConflictingCertificates,
+        },
+    };
+    for node in [1, 2] {
+        state.status.insert(node, NodeStatus::Byzantine(strategy.clone()));
+    }
+    
+    let mut attacked = state.clone();
+    for node in [1, 2] {
+        attacked = attacked.next_state(&attacked, AlpenglowAction::ByzantineVote { node, strategy: strategy.clone(), slot: 1 }).unwrap();
+    }
+    // Each honest node votes for the block the coalition showed it
+    for (node, block) in [(3, 1), (4, 2)] {
+        attacked = attacked.next_state(&attacked, AlpenglowAction::Vote { node, slot: 1, block, path: VotePath::Slow }).unwrap();
+    }
+    let delivered = deliver_all(attacked);
+    
+    // With half the stake Byzantine, each honest view certifies a different block
+    assert_eq!(delivered.certifiable_in_view(3, 1, VotePath::Slow), Some((1, 300)));
+    assert_eq!(delivered.certifiable_in_view(4, 1, VotePath::Slow), Some((2, 300)));
+    assert_eq!(delivered.certifiable_in_view(3, 1, VotePath::Fast), None);
+}
+
+/// Five equal validators where node 1, the leader of slot 1, misbehaves as `behavior`
+fn leader_attack_state(behavior: LeaderAttackType) -> AlpenglowState {
+    let nodes = vec![1, 2, 3, 4, 5];
+    let stake_distribution = nodes.iter().map(|&node| (node, 100)).collect();
+    let mut state = AlpenglowState::new(nodes, stake_distribution);
+    state.network_state.packet_loss_rate = 0.0;
+    state.status.insert(1, NodeStatus::Byzantine(ByzantineStrategy::LeaderAttack { behavior }));
+    state
+}
+
+fn propose(state: &AlpenglowState, block: Block) -> AlpenglowState {
+    state.next_state(state, AlpenglowAction::ProposeBlock {
+        leader: 1,
+        slot: 1,
+        block,
+        window: state.current_window.clone(),
+    }).unwrap()
+}
+
+fn property_holds(state: &AlpenglowState, name: &str) -> bool {
+    let property = state.properties().into_iter().find(|property| property.name == name).unwrap();
+    (property.condition)(state, state)
+}
+
+#[test]
+fn test_withheld_proposal_is_skipped() {
+    let state = leader_attack_state(LeaderAttackType::WithholdProposal);
+    let mut state = propose(&state, Block { id: 1, parent: 0 });
+    assert!(state.proposals[&1].blocks.is_empty());
+    assert!(!state.has_faulty_proposal(1));
+    
+    // Nothing to vote for, so honest nodes wait out their timeouts and skip the slot
+    state = state.next_state(&state, AlpenglowAction::Vote { node: 2, slot: 1, block: 1, path: VotePath::Fast }).unwrap();
+    assert!(state.votes[&2][&1].is_empty());
+    for node in 2..=5 {
+        for _ in 0..3 {
+            state = state.next_state(&state, AlpenglowAction::Timeout { node, slot: 1 }).unwrap();
+        }
+    }
+    state = state.next_state(&state, AlpenglowAction::SkipCert { slot: 1 }).unwrap();
+    assert_eq!(state.skip_certs[&1].total_stake, 400);
+    assert!(property_holds(&state, "faulty_leader_slots_skipped"));
+    assert!(property_holds(&state, "no_conflicting_finalization"));
+}
+
+#[test]
+fn test_skip_from_timeouts_alone_needs_a_faulty_leader() {
+    let nodes = vec![1, 2, 3, 4, 5];
+    let stake_distribution = nodes.iter().map(|&node| (node, 100)).collect();
+    let state = AlpenglowState::new(nodes, stake_distribution);
+    let time_out_and_skip = |mut state: AlpenglowState| {
+        for node in 1..=5 {
+            for _ in 0..3 {
+                state = state.next_state(&state, AlpenglowAction::Timeout { node, slot: 1 }).unwrap();
+            }
+        }
+        state.next_state(&state, AlpenglowAction::SkipCert { slot: 1 }).unwrap()
+    };
+    
+    // Without a faulty proposal, timeouts alone carry no stake
+    assert!(!time_out_and_skip(state.clone()).skip_certs.contains_key(&1));
+    
+    // The skip certificate still forms from the votes cast in the slot
+    let mut voted = state;
+    for node in 1..=5 {
+        voted = voted.next_state(&voted, AlpenglowAction::Vote { node, slot: 1, block: 0, path: VotePath::Slow }).unwrap();
+    }
+    let skipped = time_out_and_skip(voted);
+    assert_eq!(skipped.skip_certs[&1].timeout_votes.len(), 5);
+    assert_eq!(skipped.skip_certs[&1].total_stake, 500);
+}
+
+#[test]
+fn test_equivocating_leader_splits_relays() {
+    let state = leader_attack_state(LeaderAttackType::EquivocateProposal);
+    let block = Block { id: 1, parent: 0 };
+    let conflicting = conflicting_block(&block);
+    let mut state = propose(&state, block.clone());
+    assert!(state.has_faulty_proposal(1));
+    
+    let erasure_block = state.create_erasure_coded_block(block, 0.5);
+    state = state.next_state(&state, AlpenglowAction::PropagateErasureBlock { node: 1, slot: 1, erasure_block }).unwrap();
+    let state = deliver_all(state);
+    
+    // Honest nodes end up holding chunks of both blocks
+    let honest_holders = |block_id: BlockId| -> bool {
+        state.chunk_availability.iter()
+            .any(|(&(id, _), holders)| id == block_id && holders.iter().any(|&node| node != 1))
+    };
+    assert!(honest_holders(1));
+    assert!(honest_holders(conflicting.id));
+    
+    // The proof of equivocation makes honest nodes skip at once and refuse to vote
+    let timed_out = state.next_state(&state, AlpenglowAction::Timeout { node: 2, slot: 1 }).unwrap();
+    assert_eq!(timed_out.timeouts[&2][&1].count, 3);
+    for block_id in [1, conflicting.id] {
+        let voted = state.next_state(&state, AlpenglowAction::Vote { node: 2, slot: 1, block: block_id, path: VotePath::Slow }).unwrap();
+        assert!(voted.votes[&2][&1].is_empty());
+    }
+    
+    // Certifying or finalizing either block would break the properties
+    assert!(property_holds(&state, "faulty_leader_slots_skipped"));
+    let mut finalized = state.clone();
+    finalized.certificates.insert(1, Certificate {
+        votes: HashSet::new(),
+        slot: 1,
+        block: conflicting.id,
+        total_stake: 400,
+        path: VotePath::Slow,
+    });
+    finalized.ledger.push(FinalizedBlock { slot: 1, block_id: conflicting.id, finalization_time: 0, total_stake: 400 });
+    assert!(!property_holds(&finalized, "faulty_leader_slots_skipped"));
+    assert!(!property_holds(&finalized, "no_conflicting_finalization"));
+}
+
+#[test]
+fn test_equivocating_dispatch_records_both_blocks() {
+    // Without a ProposeBlock first, dispatch alone must leave no block to vote for
+    let state = leader_attack_state(LeaderAttackType::EquivocateProposal);
+    let block = Block { id: 1, parent: 0 };
+    let erasure_block = state.create_erasure_coded_block(block.clone(), 0.5);
+    let mut state = state.next_state(&state, AlpenglowAction::PropagateErasureBlock { node: 1, slot: 1, erasure_block }).unwrap();
+    let proposed: Vec<BlockId> = state.proposals[&1].blocks.iter().map(|block| block.id).collect();
+    assert_eq!(proposed, vec![1, conflicting_block(&block).id]);
+    assert_eq!(state.votable_block(1), None);
+    
+    // Once every honest node has given up on the slot, the property expects a skip certificate
+    for node in 2..=5 {
+        state = state.next_state(&state, AlpenglowAction::Timeout { node, slot: 1 }).unwrap();
+    }
+    assert!(state.skip_certs.is_empty());
+    assert!(property_holds(&state, "faulty_leader_slots_skipped"));
+    let skipped = state.next_state(&state, AlpenglowAction::SkipCert { slot: 1 }).unwrap();
+    assert_eq!(skipped.skip_certs[&1].total_stake, 400);
+    
+    // The conflicting id differs from the original even when bit 31 is already set
+    let high = Block { id: 1 << 31 | 7, parent: 0 };
+    assert_ne!(conflicting_block(&high).id, high.id);
+    assert_eq!(conflicting_block(&conflicting_block(&high)), high);
+}
+
+#[test]
+fn test_unready_parent_and_late_proposals() {
+    let state = leader_attack_state(LeaderAttackType::UnreadyParent);
+    let state = propose(&state, Block { id: 1, parent: 0 });
+    let parent = state.proposals[&1].blocks[0].parent;
+    assert!(!state.parent_ready(parent));
+    assert!(state.has_faulty_proposal(1));
+    assert_eq!(state.votable_block(1), None);
+    
+    // A late proposal is valid, but honest nodes can only vote once it arrives
+    let state = leader_attack_state(LeaderAttackType::LateProposal { delay_ms: 50 });
+    let mut state = propose(&state, Block { id: 1, parent: 0 });
+    assert!(!state.has_faulty_proposal(1));
+    let vote = AlpenglowAction::Vote { node: 2, slot: 1, block: 1, path: VotePath::Fast };
+    assert!(state.next_state(&state, vote.clone()).unwrap().votes[&2][&1].is_empty());
+    
+    state.global_time = 50;
+    assert_eq!(state.votable_block(1), Some(1));
+    assert_eq!(state.next_state(&state, vote).unwrap().votes[&2][&1].len(), 1);
+}
@@ -100,7 +100,7 @@ fn test_slashing_severities() {
     
     let mut state = AlpenglowState::new(nodes, stake_dist);
     
-    let severities = vec![
+    let severities = [
         (SlashingSeverity::Minor, 0.05),
         (SlashingSeverity::Moderate, 0.15),
         (SlashingSeverity::Severe, 0.30),
@@ -359,7 +359,7 @@ fn test_economic_attack_resistance() {
     assert!(state.economic_state.total_slashed >= expected_min_slash);
     
     // Honest validators should remain honest and keep their stake
-    for honest_validator in vec![2, 3] {
+    for honest_validator in [2, 3] {
         assert!(matches!(state.status[&honest_validator], NodeStatus::Honest));
         assert_eq!(state.economic_state.validator_balances[&honest_validator], 
                   state.stake_distribution[&honest_validator]);
@@ -1,6 +1,7 @@
 use crate::*;
 use stateright::Model;
 use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 #[test]
 fn test_basic_message_sending() {
@@ -39,6 +40,8 @@ fn test_message_delivery() {
     }
     
     let mut state = AlpenglowState::new(nodes, stake_distribution);
+    let log = Arc::new(Mutex::new(DeliveryLog::default()));
+    state.metrics = MetricsHandle::shared(log.clone());
     let model = state.clone();
     
     // Send a vote message
@@ -69,7 +72,9 @@ fn test_message_delivery() {
     
     // Message should be delivered and removed from pending
     assert_eq!(final_state.message_queue.pending_messages.len(), 0);
-    assert_eq!(final_state.message_queue.delivered_messages.len(), 1);
+    let deliveries = &log.lock().unwrap().deliveries;
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].id, message_id);
     
     // Vote should be delivered to receiving node
     let delivered_votes = &final_state.votes[&2][&1];
@@ -97,7 +102,7 @@ fn test_latency_models() {
         max_ms: 100 
     };
     let uniform_latency = model.calculate_latency(&state_uniform, 1, 2);
-    assert!(uniform_latency >= 10 && uniform_latency <= 100);
+    assert!((10..=100).contains(&uniform_latency));
     
     // Test normal distribution model
     let mut state_normal = state.clone();
@@ -291,10 +296,23 @@ fn test_message_priority_ordering() {
     stake_distribution.insert(1, 100);
     stake_distribution.insert(2, 100);
     
-    let state = AlpenglowState::new(nodes, stake_distribution);
+    let mut state = AlpenglowState::new(nodes, stake_distribution);
+    // Only serialization delay here; congestion latency is covered separately
+    state.network_state.congestion_state.congestion_threshold = f64::INFINITY;
     let model = state.clone();
     
-    // Send messages with different priorities
+    // A 1 KB/s link: each 1000-byte gossip message occupies it for a second
+    let state = model.next_state(&state, AlpenglowAction::AdjustBandwidth {
+        from: 1, to: 2, new_bandwidth: 1000,
+    }).unwrap();
+    
+    let normal_action = AlpenglowAction::SendMessage {
+        from: 1,
+        to: 2,
+        content: MessageContent::Gossip { data: vec![0; 968] },
+        priority: MessagePriority::Normal,
+    };
+    
     let critical_action = AlpenglowAction::SendMessage {
         from: 1,
         to: 2,
@@ -308,27 +326,40 @@ fn test_message_priority_ordering() {
         priority: MessagePriority::Critical,
     };
     
-    let normal_action = AlpenglowAction::SendMessage {
-        from: 1,
-        to: 2,
-        content: MessageContent::Heartbeat { sequence: 1 },
-        priority: MessagePriority::Normal,
-    };
-    
-    let state1 = model.next_state(&state, critical_action).unwrap();
+    let state1 = model.next_state(&state, normal_action.clone()).unwrap();
     let state2 = model.next_state(&state1, normal_action).unwrap();
+    let state3 = model.next_state(&state2, critical_action).unwrap();
+    assert_eq!(state3.message_queue.pending_messages.len(), 3);
+    
+    let delivery = |priority: MessagePriority| -> Vec<Timestamp> {
+        state3.message_queue.pending_messages.iter()
+            .filter(|msg| msg.priority == priority)
+            .map(|msg| msg.scheduled_delivery_time)
+            .collect()
+    };
     
-    // Both messages should be queued
-    assert_eq!(state2.message_queue.pending_messages.len(), 2);
-    
-    // Messages should have different priorities
-    let priorities: Vec<_> = state2.message_queue.pending_messages
-        .iter()
-        .map(|msg| &msg.priority)
+    // The vote cannot preempt the gossip already on the wire, but overtakes the queued one
+    let vote_bytes = MessageContent::Vote(Vote { node: 1, slot: 1, block: 0, path: VotePath::Fast, stake: 100 }).wire_size();
+    let vote_wire_time = transmission_time(vote_bytes, 1000);
+    assert_eq!(delivery(MessagePriority::Critical), vec![50 + 1000 + vote_wire_time]);
+    assert_eq!(delivery(MessagePriority::Normal), vec![50 + 1000, 50 + 2000 + vote_wire_time]);
+    
+    // When a receiver has several messages due, only the most urgent are offered
+    let mut due = state3.clone();
+    due.global_time = 10_000;
+    let mut actions = Vec::new();
+    due.actions(&due, &mut actions);
+    let offered: Vec<u64> = actions.iter()
+        .filter_map(|a| match a {
+            AlpenglowAction::DeliverMessage { message_id } => Some(*message_id),
+            _ => None,
+        })
         .collect();
-    
-    assert!(priorities.contains(&&MessagePriority::Critical));
-    assert!(priorities.contains(&&MessagePriority::Normal));
+    let critical_id = due.message_queue.pending_messages.iter()
+        .find(|msg| msg.priority == MessagePriority::Critical)
+        .unwrap()
+        .id;
+    assert_eq!(offered, vec![critical_id]);
 }
 
 #[test]
@@ -437,4 +468,1054 @@ fn test_bandwidth_adjustment() {
     // Bandwidth limit should be set
     assert_eq!(limited_state.network_state.bandwidth_limits.len(), 1);
     assert_eq!(limited_state.network_state.bandwidth_limits[&(1, 2)], 1000);
-}
\ No newline at end of file
+}
+
+/// Send one heartbeat from node 0 to every other node at the same tick
+fn broadcast_heartbeats(state: &AlpenglowState, receivers: u32) -> AlpenglowState {
+    let mut state = state.clone();
+    for to in 1..=receivers {
+        state = state.next_state(&state, AlpenglowAction::SendMessage {
+            from: 0,
+            to,
+            content: MessageContent::Heartbeat { sequence: 0 },
+            priority: MessagePriority::Normal,
+        }).unwrap();
+    }
+    state
+}
+
+fn network_test_state(nodes: u32) -> AlpenglowState {
+    let nodes: Vec<NodeId> = (0..=nodes).collect();
+    let stake_distribution = nodes.iter().map(|&node| (node, 100)).collect();
+    AlpenglowState::new(nodes, stake_distribution)
+}
+
+#[test]
+fn test_seeded_latency_is_reproducible() {
+    let mut state = network_test_state(50);
+    state.network_state.latency_model = LatencyModel::Uniform { min_ms: 10, max_ms: 100 };
+    
+    let latencies = |seed: u64| -> Vec<Timestamp> {
+        let mut seeded = state.clone();
+        seeded.network_state.seed = seed;
+        broadcast_heartbeats(&seeded, 50).message_queue.pending_messages.iter()
+            .map(|msg| msg.scheduled_delivery_time)
+            .collect()
+    };
+    
+    assert_eq!(latencies(7), latencies(7));
+    assert_ne!(latencies(7), latencies(8));
+    // Links sending at the same tick no longer share a latency
+    let distinct: HashSet<Timestamp> = latencies(7).into_iter().collect();
+    assert!(distinct.len() > 10);
+}
+
+#[test]
+fn test_latency_and_loss_draws_share_the_message_id() {
+    let mut state = network_test_state(1);
+    state.network_state.latency_model = LatencyModel::Uniform { min_ms: 10, max_ms: 1000 };
+    
+    // calculate_latency predicts the next send, which is drawn for the id the message gets
+    let predicted = state.calculate_latency(&state, 0, 1);
+    let sent = broadcast_heartbeats(&state, 1);
+    let message = &sent.message_queue.pending_messages[0];
+    assert_eq!(message.id, state.message_queue.message_counter);
+    assert_eq!(message.scheduled_delivery_time - message.send_time, predicted);
+    assert_eq!(predicted, state.message_latency(&state, message.id, 0, 1));
+}
+
+#[test]
+fn test_normal_latency_is_bell_shaped() {
+    let mut state = network_test_state(400);
+    state.network_state.latency_model = LatencyModel::Normal { mean_ms: 200, std_dev_ms: 20 };
+    
+    let latencies: Vec<f64> = broadcast_heartbeats(&state, 400).message_queue.pending_messages.iter()
+        .map(|msg| msg.scheduled_delivery_time as f64)
+        .collect();
+    let mean = latencies.iter().sum::<f64>() / latencies.len() as f64;
+    let within_one_sigma = latencies.iter().filter(|&&l| (l - 200.0).abs() <= 20.0).count() as f64
+        / latencies.len() as f64;
+    
+    assert!((mean - 200.0).abs() < 5.0, "mean {}", mean);
+    // About 68% of a normal distribution lies within one standard deviation; a uniform
+    // over the same ±2σ range would put 50% there
+    assert!((within_one_sigma - 0.68).abs() < 0.08, "within 1σ: {}", within_one_sigma);
+}
+
+#[test]
+fn test_seeded_packet_loss_is_independent_per_link() {
+    let state = network_test_state(200);
+    let state = state.next_state(&state, AlpenglowAction::InjectNetworkFailure {
+        failure: NetworkFailure {
+            failure_type: FailureType::PacketLoss { loss_rate: 0.5 },
+            start_time: 0,
+            duration: 100,
+            affected_nodes: vec![],
+            severity: 0.5,
+        },
+    }).unwrap();
+    
+    // Half the links lose their message at the same tick, rather than all or none
+    let delivered = broadcast_heartbeats(&state, 200).message_queue.pending_messages.len();
+    assert!((70..=130).contains(&delivered), "{} of 200 delivered", delivered);
+}
+
+#[test]
+fn test_baseline_packet_loss_rate_applies_without_failures() {
+    let mut state = network_test_state(200);
+    state.network_state.packet_loss_rate = 0.5;
+    let delivered = broadcast_heartbeats(&state, 200).message_queue.pending_messages.len();
+    assert!((70..=130).contains(&delivered), "{} of 200 delivered", delivered);
+    
+    // It combines with an active packet-loss failure
+    let state = state.next_state(&state, AlpenglowAction::InjectNetworkFailure {
+        failure: NetworkFailure {
+            failure_type: FailureType::PacketLoss { loss_rate: 0.5 },
+            start_time: 0,
+            duration: 100,
+            affected_nodes: vec![],
+            severity: 0.5,
+        },
+    }).unwrap();
+    assert!((state.packet_loss_rate() - 0.75).abs() < 1e-12);
+    
+    let mut lossless = network_test_state(200);
+    lossless.network_state.packet_loss_rate = 0.0;
+    assert_eq!(broadcast_heartbeats(&lossless, 200).message_queue.pending_messages.len(), 200);
+}
+
+#[test]
+fn test_nondeterministic_network_branches_on_loss_and_delay() {
+    let mut state = network_test_state(2);
+    state.network_state.randomness = NetworkRandomness::Nondeterministic;
+    state.network_state.latency_model = LatencyModel::Uniform { min_ms: 10, max_ms: 100 };
+    let state = state.next_state(&state, AlpenglowAction::InjectNetworkFailure {
+        failure: NetworkFailure {
+            failure_type: FailureType::PacketLoss { loss_rate: 0.1 },
+            start_time: 0,
+            duration: 100,
+            affected_nodes: vec![],
+            severity: 0.1,
+        },
+    }).unwrap();
+    
+    // The message goes out at the lowest latency and is never dropped by a draw
+    let state = broadcast_heartbeats(&state, 1);
+    let message = state.message_queue.pending_messages[0].clone();
+    assert_eq!(message.scheduled_delivery_time, 10);
+    assert_eq!(message.latency_slack, 90);
+    
+    let mut actions = Vec::new();
+    state.actions(&state, &mut actions);
+    assert!(actions.contains(&AlpenglowAction::DelayMessage { message_id: message.id, delay_ms: 90 }));
+    assert!(actions.iter().any(|a| matches!(a, AlpenglowAction::DropMessage { message_id, .. } if *message_id == message.id)));
+    
+    // Taking the delay branch uses up the slack
+    let delayed = state.next_state(&state, AlpenglowAction::DelayMessage { message_id: message.id, delay_ms: 90 }).unwrap();
+    assert_eq!(delayed.message_queue.pending_messages[0].scheduled_delivery_time, 100);
+    let mut actions = Vec::new();
+    delayed.actions(&delayed, &mut actions);
+    assert!(!actions.iter().any(|a| matches!(a, AlpenglowAction::DelayMessage { .. })));
+}
+
+#[test]
+fn test_message_wire_sizes() {
+    let vote = |node| Vote { node, slot: 1, block: 1, path: VotePath::Fast, stake: 100 };
+    let certificate = |voters: u32| MessageContent::Certificate(Certificate {
+        votes: (0..voters).map(vote).collect(),
+        slot: 1,
+        block: 1,
+        total_stake: 100 * voters as StakeAmount,
+        path: VotePath::Fast,
+    });
+    
+    assert_eq!(MessageContent::Vote(vote(0)).wire_size(), MESSAGE_HEADER_BYTES + VOTE_WIRE_BYTES);
+    assert_eq!(
+        certificate(10).wire_size() - certificate(5).wire_size(),
+        5 * VOTE_WIRE_BYTES
+    );
+    assert_eq!(
+        MessageContent::Gossip { data: vec![0; 1000] }.wire_size(),
+        MESSAGE_HEADER_BYTES + 1000
+    );
+    assert!(MessageContent::Heartbeat { sequence: 0 }.wire_size() < MessageContent::Vote(vote(0)).wire_size());
+}
+
+#[test]
+fn test_bandwidth_limit_serializes_link() {
+    let mut state = network_test_state(2);
+    state.network_state.congestion_state.congestion_threshold = f64::INFINITY;
+    let state = state.next_state(&state, AlpenglowAction::AdjustBandwidth {
+        from: 0, to: 1, new_bandwidth: 1000,
+    }).unwrap();
+    
+    let gossip = MessageContent::Gossip { data: vec![0; 968] }; // 1000 bytes with the header
+    let mut sent = state.clone();
+    for to in [1, 1, 2] {
+        sent = sent.next_state(&sent, AlpenglowAction::SendMessage {
+            from: 0, to, content: gossip.clone(), priority: MessagePriority::Normal,
+        }).unwrap();
+    }
+    
+    // One second per message on the 1 KB/s link, queued back to back; link 0 -> 2 is unlimited
+    let delivery: Vec<Timestamp> = sent.message_queue.pending_messages.iter()
+        .map(|msg| msg.scheduled_delivery_time)
+        .collect();
+    assert_eq!(delivery, vec![50 + 1000, 50 + 2000, 50]);
+}
+
+#[test]
+fn test_bandwidth_reduction_throttles_affected_links() {
+    let state = network_test_state(3);
+    let state = state.next_state(&state, AlpenglowAction::InjectNetworkFailure {
+        failure: NetworkFailure {
+            failure_type: FailureType::BandwidthReduction { factor: 0.001 },
+            start_time: 0,
+            duration: 100,
+            affected_nodes: vec![1],
+            severity: 0.5,
+        },
+    }).unwrap();
+    
+    // Links touching node 1 drop to 1 KB/s; other links stay unthrottled
+    assert_eq!(state.link_bandwidth(0, 1), Some(DEFAULT_NODE_BANDWIDTH / 1000));
+    assert_eq!(state.link_bandwidth(1, 2), Some(DEFAULT_NODE_BANDWIDTH / 1000));
+    assert_eq!(state.link_bandwidth(0, 2), None);
+    
+    let gossip = MessageContent::Gossip { data: vec![0; 968] };
+    let mut sent = state.clone();
+    for to in [1, 2] {
+        sent = sent.next_state(&sent, AlpenglowAction::SendMessage {
+            from: 0, to, content: gossip.clone(), priority: MessagePriority::Normal,
+        }).unwrap();
+    }
+    let delivery: Vec<Timestamp> = sent.message_queue.pending_messages.iter()
+        .map(|msg| msg.scheduled_delivery_time)
+        .collect();
+    assert_eq!(delivery, vec![50 + 1000, 50]);
+    
+    // Once the failure expires the link is back to full speed
+    let mut later = state.clone();
+    later.global_time = 100;
+    assert_eq!(later.link_bandwidth(0, 1), None);
+}
+
+/// Time at which node 0 first holds fast-quorum stake of votes for block 1 in slot 1;
+/// panics if that takes more than a minute of model time
+fn quorum_observed_at(model: &AlpenglowState, mut state: AlpenglowState) -> Timestamp {
+    let deadline = state.global_time + 60_000;
+    loop {
+        assert!(state.global_time <= deadline, "node 0 saw no fast quorum by {}ms", deadline);
+        let observed: StakeAmount = state.votes[&0][&1].iter()
+            .filter(|vote| vote.block == 1)
+            .map(|vote| vote.node)
+            .collect::<HashSet<_>>()
+            .iter()
+            .map(|node| state.stake_distribution[node])
+            .sum();
+        if observed >= state.fast_quorum_stake() {
+            return state.global_time;
+        }
+        let due: Vec<u64> = state.message_queue.pending_messages.iter()
+            .filter(|msg| msg.scheduled_delivery_time <= state.global_time)
+            .map(|msg| msg.id)
+            .collect();
+        for message_id in due {
+            state = model.next_state(&state, AlpenglowAction::DeliverMessage { message_id }).unwrap();
+        }
+        state = model.next_state(&state, AlpenglowAction::AdvanceTime { delta: 1 }).unwrap();
+    }
+}
+
+#[test]
+fn test_priority_inversion_delays_finalization() {
+    let votes_sent_as = |vote_priority: MessagePriority| -> Timestamp {
+        let mut state = network_test_state(4);
+        let model = state.clone();
+        
+        // Every link into the collector is congested with five seconds of gossip
+        for from in 1..=4 {
+            state.network_state.bandwidth_limits.insert((from, 0), 1000);
+            for _ in 0..5 {
+                state = model.next_state(&state, AlpenglowAction::SendMessage {
+                    from,
+                    to: 0,
+                    content: MessageContent::Gossip { data: vec![0; 968] },
+                    priority: MessagePriority::Normal,
+                }).unwrap();
+            }
+        }
+        for node in 0..=4 {
+            let vote = Vote { node, slot: 1, block: 1, path: VotePath::Fast, stake: 100 };
+            state.votes.get_mut(&node).unwrap().get_mut(&1).unwrap().push(vote.clone());
+            if node != 0 {
+                state = model.next_state(&state, AlpenglowAction::SendMessage {
+                    from: node,
+                    to: 0,
+                    content: MessageContent::Vote(vote),
+                    priority: vote_priority.clone(),
+                }).unwrap();
+            }
+        }
+        quorum_observed_at(&model, state)
+    };
+    
+    let prioritized = votes_sent_as(MessagePriority::Critical);
+    let inverted = votes_sent_as(MessagePriority::Low);
+    
+    // Critical votes wait only for the gossip in flight; low-priority votes queue behind all five
+    assert!(prioritized < 1500, "prioritized quorum after {}ms", prioritized);
+    assert!(inverted > 5000, "inverted quorum after {}ms", inverted);
+}
+
+/// Deliver due messages tick by tick until `until`
+fn run_network_until(mut state: AlpenglowState, until: Timestamp) -> AlpenglowState {
+    let model = state.clone();
+    while state.global_time < until {
+        let due: Vec<u64> = state.message_queue.pending_messages.iter()
+            .filter(|msg| msg.scheduled_delivery_time <= state.global_time)
+            .map(|msg| msg.id)
+            .collect();
+        for message_id in due {
+            state = model.next_state(&state, AlpenglowAction::DeliverMessage { message_id }).unwrap();
+        }
+        state = model.next_state(&state, AlpenglowAction::AdvanceTime { delta: 1 }).unwrap();
+    }
+    state
+}
+
+fn send_vote_over_failed_link(policy: Option<RetransmissionPolicy>, outage: Timestamp) -> (AlpenglowState, Arc<Mutex<RunAnalytics>>) {
+    let mut state = network_test_state(2);
+    state.network_state.reliable_delivery = policy;
+    let analytics = Arc::new(Mutex::new(RunAnalytics::default()));
+    state.metrics = MetricsHandle::shared(analytics.clone());
+    let state = state.next_state(&state, AlpenglowAction::InjectNetworkFailure {
+        failure: NetworkFailure {
+            failure_type: FailureType::LinkFailure { from: 1, to: 2 },
+            start_time: 0,
+            duration: outage,
+            affected_nodes: vec![1, 2],
+            severity: 1.0,
+        },
+    }).unwrap();
+    let state = state.next_state(&state, AlpenglowAction::SendMessage {
+        from: 1,
+        to: 2,
+        content: MessageContent::Vote(Vote { node: 1, slot: 1, block: 1, path: VotePath::Fast, stake: 100 }),
+        priority: MessagePriority::Critical,
+    }).unwrap();
+    (state, analytics)
+}
+
+#[test]
+fn test_reliable_vote_arrives_after_link_heals() {
+    let policy = RetransmissionPolicy { ack_timeout: 100, backoff_factor: 2, max_retries: 5 };
+    let has_vote = |state: &AlpenglowState| state.votes[&2][&1].iter().any(|vote| vote.node == 1);
+    
+    // Without acks the vote is lost with the link
+    let (unreliable, _) = send_vote_over_failed_link(None, 300);
+    assert!(!has_vote(&run_network_until(unreliable, 2000)));
+    
+    // Retransmits at 100ms (link still down) and 100 + 200ms (healed) get it through
+    let (reliable, analytics) = send_vote_over_failed_link(Some(policy), 300);
+    let reliable = run_network_until(reliable, 2000);
+    assert!(has_vote(&reliable));
+    assert!(reliable.message_queue.unacked.is_empty());
+    
+    let stats = &analytics.lock().unwrap().reliable;
+    assert_eq!(stats.messages_sent, 1);
+    assert_eq!(stats.retransmissions, 2);
+    assert_eq!(stats.acknowledged, 1);
+    assert_eq!(stats.abandoned, 0);
+    // Two retransmissions and one ack for a single vote
+    assert_eq!(stats.overhead(), 3.0);
+}
+
+#[test]
+fn test_reliable_delivery_gives_up_after_retry_cap() {
+    let policy = RetransmissionPolicy { ack_timeout: 100, backoff_factor: 2, max_retries: 3 };
+    let (state, analytics) = send_vote_over_failed_link(Some(policy), 100_000);
+    let state = run_network_until(state, 5000);
+    
+    // Retries at 100, 300 and 700ms, then the sender gives up at 1500ms
+    let stats = &analytics.lock().unwrap().reliable;
+    assert_eq!(stats.retransmissions, 3);
+    assert_eq!(stats.abandoned, 1);
+    assert!(state.message_queue.unacked.is_empty());
+    assert!(state.message_queue.pending_messages.is_empty());
+}
+
+#[test]
+fn test_reliable_delivery_only_tracks_critical_messages() {
+    let mut state = network_test_state(2);
+    state.network_state.reliable_delivery = Some(RetransmissionPolicy::default());
+    let log = Arc::new(Mutex::new(DeliveryLog::default()));
+    let analytics = Arc::new(Mutex::new(RunAnalytics::default()));
+    state.metrics = MetricsHandle::fan_out(vec![log.clone(), analytics.clone()]);
+    
+    let state = state.next_state(&state, AlpenglowAction::SendMessage {
+        from: 1,
+        to: 2,
+        content: MessageContent::Heartbeat { sequence: 1 },
+        priority: MessagePriority::Normal,
+    }).unwrap();
+    let state = run_network_until(state, 200);
+    
+    // Heartbeats are neither acknowledged nor retransmitted
+    assert!(state.message_queue.unacked.is_empty());
+    assert_eq!(analytics.lock().unwrap().reliable, ReliableDeliveryStats::default());
+    assert_eq!(log.lock().unwrap().deliveries.len(), 1);
+}
+
+fn send_heartbeat(state: &AlpenglowState, from: NodeId, to: NodeId) -> bool {
+    let sent = state.next_state(state, AlpenglowAction::SendMessage {
+        from,
+        to,
+        content: MessageContent::Heartbeat { sequence: 0 },
+        priority: MessagePriority::Normal,
+    }).unwrap();
+    !sent.message_queue.pending_messages.is_empty()
+}
+
+#[test]
+fn test_three_way_partition() {
+    let state = network_test_state(5);
+    let state = state.next_state(&state, AlpenglowAction::PartitionNetwork {
+        groups: vec![HashSet::from([0, 1]), HashSet::from([2, 3]), HashSet::from([4])],
+        one_way: vec![],
+    }).unwrap();
+    
+    for (from, to, reachable) in [(0, 1, true), (2, 3, true), (0, 2, false), (3, 4, false), (4, 1, false)] {
+        assert_eq!(state.can_node_communicate(from, to), reachable, "{} -> {}", from, to);
+        assert_eq!(send_heartbeat(&state, from, to), reachable, "{} -> {}", from, to);
+    }
+    // Node 5 is in no group and keeps talking to everyone
+    assert!(state.can_node_communicate(5, 0) && send_heartbeat(&state, 4, 5));
+}
+
+#[test]
+fn test_one_way_partition() {
+    let state = network_test_state(3);
+    let state = state.next_state(&state, AlpenglowAction::PartitionNetwork {
+        groups: vec![HashSet::from([0]), HashSet::from([1, 2, 3])],
+        one_way: vec![(0, 1)],
+    }).unwrap();
+    
+    // Node 0 can still broadcast but hears nothing back
+    assert!(state.can_node_communicate(0, 2) && send_heartbeat(&state, 0, 2));
+    assert!(!state.can_node_communicate(2, 0) && !send_heartbeat(&state, 2, 0));
+}
+
+#[test]
+fn test_partial_partition_failure_is_applied() {
+    let state = network_test_state(3);
+    let state = state.next_state(&state, AlpenglowAction::InjectNetworkFailure {
+        failure: NetworkFailure {
+            failure_type: FailureType::PartialPartition { partition_a: vec![0, 1], partition_b: vec![2] },
+            start_time: 0,
+            duration: 100,
+            affected_nodes: vec![0, 1, 2],
+            severity: 1.0,
+        },
+    }).unwrap();
+    
+    assert!(!state.can_node_communicate(0, 2) && !send_heartbeat(&state, 0, 2));
+    assert!(!state.can_node_communicate(2, 1) && !send_heartbeat(&state, 2, 1));
+    assert!(state.can_node_communicate(0, 1) && send_heartbeat(&state, 0, 1));
+    // Node 3 is outside the partial partition
+    assert!(state.can_node_communicate(3, 2) && send_heartbeat(&state, 3, 2));
+    
+    let mut healed = state.clone();
+    healed.global_time = 100;
+    assert!(healed.can_node_communicate(0, 2) && send_heartbeat(&healed, 0, 2));
+}
+
+#[test]
+fn test_stake_based_partition_scenarios() {
+    let nodes: Vec<NodeId> = (0..10).collect();
+    let stake_distribution: HashMap<NodeId, StakeAmount> = nodes.iter()
+        .map(|&node| (node, 1000 + node as StakeAmount * 100))
+        .collect();
+    let state = AlpenglowState::new(nodes, stake_distribution);
+    let total = state.total_stake() as f64;
+    let share = |group: &HashSet<NodeId>| -> f64 {
+        group.iter().map(|node| state.stake_distribution[node]).sum::<StakeAmount>() as f64 / total
+    };
+    
+    let split = state.stake_split(&[0.6, 0.4]);
+    assert!((share(&split[0]) - 0.6).abs() < 0.05);
+    assert!((share(&split[1]) - 0.4).abs() < 0.05);
+    
+    let split = state.stake_split(&[0.2, 0.2, 0.6]);
+    for (group, target) in split.iter().zip([0.2, 0.2, 0.6]) {
+        assert!((share(group) - target).abs() < 0.05, "{:?}", split);
+    }
+    assert_eq!(split.iter().map(|g| g.len()).sum::<usize>(), 10);
+    
+    // The checker is offered the leader-isolated, 60/40 and 20/20/60 splits
+    let leader = state.get_leader_for_slot(state.current_slot);
+    let mut actions = Vec::new();
+    state.actions(&state, &mut actions);
+    let splits: Vec<&Vec<HashSet<NodeId>>> = actions.iter()
+        .filter_map(|a| match a {
+            AlpenglowAction::PartitionNetwork { groups, .. } => Some(groups),
+            _ => None,
+        })
+        .collect();
+    assert!(splits.iter().any(|groups| groups[0] == HashSet::from([leader])));
+    assert!(splits.iter().any(|groups| groups.len() == 3));
+}
+
+#[test]
+fn test_fault_schedule_activates_and_expires() {
+    let schedule = FaultSchedule::from_json(r#"{
+        "events": [
+            {
+                "failure_type": { "LinkFailure": { "from": 1, "to": 2 } },
+                "start_time": 10, "duration": 20, "affected_nodes": [1, 2], "severity": 1.0
+            },
+            {
+                "failure_type": { "LatencySpike": { "multiplier": 3.0 } },
+                "start_time": 5, "duration": 100, "affected_nodes": [], "severity": 0.5
+            }
+        ]
+    }"#).unwrap();
+    
+    let mut state = network_test_state(2);
+    state.load_fault_schedule(schedule);
+    let active_at = |state: &AlpenglowState, time: Timestamp| -> Vec<FailureType> {
+        let state = state.next_state(state, AlpenglowAction::AdvanceTime { delta: time - state.global_time }).unwrap();
+        state.network_state.failure_injections.iter().map(|f| f.failure_type.clone()).collect()
+    };
+    
+    assert!(state.network_state.failure_injections.is_empty());
+    assert_eq!(active_at(&state, 5), vec![FailureType::LatencySpike { multiplier: 3.0 }]);
+    assert_eq!(active_at(&state, 10).len(), 2);
+    assert_eq!(active_at(&state, 30), vec![FailureType::LatencySpike { multiplier: 3.0 }]);
+    assert!(active_at(&state, 105).is_empty());
+    
+    // A scenario schedule replaces the canned failures offered to the checker
+    let late = state.next_state(&state, AlpenglowAction::AdvanceTime { delta: 200 }).unwrap();
+    let mut actions = Vec::new();
+    late.actions(&late, &mut actions);
+    assert!(!actions.iter().any(|a| matches!(a, AlpenglowAction::InjectNetworkFailure { .. })));
+}
+
+#[test]
+fn test_slot_reports_record_active_failures() {
+    let mut state = network_test_state(2);
+    let analytics = Arc::new(Mutex::new(RunAnalytics::default()));
+    state.metrics = MetricsHandle::shared(analytics.clone());
+    state.load_fault_schedule(FaultSchedule {
+        events: vec![NetworkFailure {
+            failure_type: FailureType::PacketLoss { loss_rate: 0.2 },
+            start_time: 0,
+            duration: 50,
+            affected_nodes: vec![],
+            severity: 0.2,
+        }],
+    });
+    let state = state.next_state(&state, AlpenglowAction::AdvanceTime { delta: 20 }).unwrap();
+    
+    // Slot 1 finalizes while packet loss is active
+    let mut finalized = state.clone();
+    for node in 0..3 {
+        finalized = finalized.next_state(&finalized, AlpenglowAction::Vote { node, slot: 1, block: 1, path: VotePath::Fast }).unwrap();
+    }
+    let finalized = finalized.next_state(&finalized, AlpenglowAction::Certify { slot: 1, path: VotePath::Fast }).unwrap();
+    let report = analytics.lock().unwrap().slot_fault_reports[&1].clone();
+    assert_eq!(report.outcome, SlotOutcome::Finalized);
+    assert_eq!(report.resolved_at, 20);
+    assert_eq!(report.active_failures, vec![FailureType::PacketLoss { loss_rate: 0.2 }]);
+    
+    // Slot 2 is skipped after the loss has expired
+    let later = finalized.next_state(&finalized, AlpenglowAction::AdvanceTime { delta: 40 }).unwrap();
+    let skip = MessageContent::SkipCertificate(SkipCertificate { slot: 2, timeout_votes: HashSet::new(), total_stake: 300 });
+    let sent = later.next_state(&later, AlpenglowAction::SendMessage {
+        from: 0, to: 1, content: skip, priority: MessagePriority::High,
+    }).unwrap();
+    let skipped = run_network_until(sent, 200);
+    assert!(skipped.skip_certs.contains_key(&2));
+    let reports = &analytics.lock().unwrap().slot_fault_reports;
+    assert_eq!(reports[&2].outcome, SlotOutcome::Skipped);
+    assert!(reports[&2].active_failures.is_empty());
+    assert_eq!(reports.len(), 2);
+}
+
+fn gst_test_state(gst: Timestamp, delta: Timestamp) -> AlpenglowState {
+    let mut state = network_test_state(3);
+    state.status.insert(3, NodeStatus::Byzantine(ByzantineStrategy::Equivocation));
+    state.network_state.packet_loss_rate = 0.0;
+    state.network_state.partial_synchrony = Some(PartialSynchrony { gst, delta, slot_bound: 30 });
+    state.next_state(&state, AlpenglowAction::InjectNetworkFailure {
+        failure: NetworkFailure {
+            failure_type: FailureType::LinkFailure { from: 1, to: 2 },
+            start_time: 0,
+            duration: 10_000,
+            affected_nodes: vec![1, 2],
+            severity: 1.0,
+        },
+    }).unwrap()
+}
+
+fn send_vote(state: &AlpenglowState, from: NodeId, to: NodeId) -> AlpenglowState {
+    let vote = Vote { node: from, slot: 1, block: 1, path: VotePath::Fast, stake: 100 };
+    state.next_state(state, AlpenglowAction::SendMessage {
+        from, to, content: MessageContent::Vote(vote), priority: MessagePriority::Critical,
+    }).unwrap()
+}
+
+#[test]
+fn test_gst_holds_honest_messages_until_deadline() {
+    let state = gst_test_state(100, 80);
+    
+    // Before GST a failed link only delays honest traffic until GST + Δ
+    let held = send_vote(&state, 1, 2);
+    let message = &held.message_queue.pending_messages[0];
+    assert_eq!(message.deliver_by, Some(180));
+    assert_eq!(message.scheduled_delivery_time, 180);
+    assert_eq!(message.latency_slack, 0);
+    
+    // A working link delivers at its latency; the adversary may hold the message up to the deadline
+    let direct = send_vote(&state, 0, 2);
+    let message = &direct.message_queue.pending_messages[0];
+    assert_eq!(message.scheduled_delivery_time, 50);
+    assert_eq!(message.latency_slack, 130);
+    let mut actions = Vec::new();
+    direct.actions(&direct, &mut actions);
+    assert!(actions.contains(&AlpenglowAction::DelayMessage { message_id: message.id, delay_ms: 130 }));
+    
+    // Byzantine endpoints get no guarantee
+    let byzantine = send_vote(&state, 3, 2);
+    assert_eq!(byzantine.message_queue.pending_messages[0].deliver_by, None);
+    assert_eq!(byzantine.message_queue.pending_messages[0].latency_slack, 0);
+}
+
+#[test]
+fn test_gst_holds_messages_across_a_partition() {
+    let mut state = gst_test_state(100, 20);
+    state.network_state.failure_injections.clear();
+    let state = state.next_state(&state, AlpenglowAction::PartitionNetwork {
+        groups: vec![HashSet::from([0, 1]), HashSet::from([2, 3])],
+        one_way: vec![],
+    }).unwrap();
+    assert!(!state.can_node_communicate(0, 2));
+    
+    // Honest traffic across the partition is held until GST + Δ instead of being dropped
+    let held = send_vote(&state, 0, 2);
+    assert_eq!(held.message_queue.pending_messages[0].scheduled_delivery_time, 120);
+    
+    // Once due it is delivered even though the partition has not healed
+    let due = run_network_until(held, 121);
+    assert!(due.message_queue.pending_messages.is_empty());
+    assert!(due.network_partition.is_some());
+    assert!(due.votes[&2][&1].iter().any(|vote| vote.node == 0));
+    
+    // Traffic within a group is unaffected, and Byzantine traffic across it is still cut
+    assert_eq!(send_vote(&state, 0, 1).message_queue.pending_messages[0].scheduled_delivery_time, 50);
+    assert!(send_vote(&state, 3, 0).message_queue.pending_messages.is_empty());
+}
+
+#[test]
+fn test_gst_bounds_delivery_by_delta() {
+    let state = gst_test_state(100, 20);
+    let after_gst = state.next_state(&state, AlpenglowAction::AdvanceTime { delta: 150 }).unwrap();
+    
+    // After GST every honest message arrives within Δ, even with latency above Δ
+    let sent = send_vote(&after_gst, 0, 2);
+    let message = &sent.message_queue.pending_messages[0];
+    assert_eq!(message.deliver_by, Some(170));
+    assert_eq!(message.scheduled_delivery_time, 170);
+    let sent = send_vote(&after_gst, 1, 2);
+    assert_eq!(sent.message_queue.pending_messages[0].scheduled_delivery_time, 170);
+    
+    // Time cannot pass a deadline before the message is delivered
+    let at_deadline = sent.next_state(&sent, AlpenglowAction::AdvanceTime { delta: 20 }).unwrap();
+    assert!(at_deadline.has_overdue_messages());
+    let mut actions = Vec::new();
+    at_deadline.actions(&at_deadline, &mut actions);
+    assert!(!actions.iter().any(|a| matches!(a, AlpenglowAction::AdvanceTime { .. })));
+    let message_id = at_deadline.message_queue.pending_messages[0].id;
+    assert!(actions.contains(&AlpenglowAction::DeliverMessage { message_id }));
+    
+    let delivered = at_deadline.next_state(&at_deadline, AlpenglowAction::DeliverMessage { message_id }).unwrap();
+    assert!(!delivered.has_overdue_messages());
+}
+
+#[test]
+fn test_post_gst_liveness_bound() {
+    let state = gst_test_state(100, 20);
+    let liveness = state.properties().into_iter()
+        .find(|property| property.name == "post_gst_liveness")
+        .unwrap();
+    
+    // Slots idle before GST are not overdue until GST + bound
+    let before_bound = run_network_until(state.clone(), 130);
+    assert_eq!(before_bound.slot_start_times.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    assert!(before_bound.post_gst_overdue_slots().is_empty());
+    assert!((liveness.condition)(&before_bound, &before_bound));
+    
+    let past_bound = run_network_until(state.clone(), 131);
+    assert_eq!(past_bound.post_gst_overdue_slots(), vec![1, 2, 3, 4, 5]);
+    assert!(!(liveness.condition)(&past_bound, &past_bound));
+    
+    // Finalizing slot 1 in time takes it off the list
+    let mut finalized = before_bound.clone();
+    for node in 0..3 {
+        finalized = finalized.next_state(&finalized, AlpenglowAction::Vote { node, slot: 1, block: 1, path: VotePath::Slow }).unwrap();
+    }
+    let finalized = finalized.next_state(&finalized, AlpenglowAction::Certify { slot: 1, path: VotePath::Slow }).unwrap();
+    let finalized = run_network_until(finalized, 131);
+    assert_eq!(finalized.post_gst_overdue_slots(), vec![2, 3, 4, 5]);
+    
+    // Without partial synchrony there is no bound to check
+    let mut synchronous = past_bound;
+    synchronous.network_state.partial_synchrony = None;
+    assert!(synchronous.post_gst_overdue_slots().is_empty());
+}
+
+#[test]
+fn test_vote_storm_congests_link() {
+    let mut state = network_test_state(2);
+    state.network_state.packet_loss_rate = 0.0;
+    state.network_state.node_bandwidth.insert(0, 1000);
+    let vote = MessageContent::Vote(Vote { node: 0, slot: 1, block: 0, path: VotePath::Fast, stake: 100 });
+    
+    let mut stormed = state.clone();
+    for _ in 0..8 {
+        stormed = stormed.next_state(&stormed, AlpenglowAction::SendMessage {
+            from: 0, to: 1, content: vote.clone(), priority: MessagePriority::Critical,
+        }).unwrap();
+    }
+    
+    // Utilization follows the bytes in flight relative to the sender's 1 KB/s uplink
+    let in_flight = 8 * vote.wire_size();
+    assert_eq!(stormed.bytes_in_flight(0, 1), in_flight);
+    let utilization = stormed.network_state.congestion_state.current_utilization[&(0, 1)];
+    assert_eq!(utilization, in_flight as f64 / 1000.0);
+    assert!(!stormed.network_state.congestion_state.current_utilization.contains_key(&(0, 2)));
+    
+    // Past the threshold, queueing adds latency in proportion to utilization
+    assert_eq!(stormed.calculate_latency(&stormed, 0, 1), 50 + (50.0 * utilization) as u64);
+    assert_eq!(stormed.calculate_latency(&stormed, 0, 2), 50);
+    
+    // Once delivered, congestion clears by `recovery_rate` per tick rather than at once
+    let latest_delivery = stormed.message_queue.pending_messages.iter().map(|msg| msg.scheduled_delivery_time).max().unwrap();
+    let drained = run_network_until(stormed, latest_delivery + 1);
+    assert_eq!(drained.bytes_in_flight(0, 1), 0);
+    let remaining = drained.network_state.congestion_state.current_utilization[&(0, 1)];
+    assert!(remaining > 0.0 && remaining < utilization);
+    let next_tick = drained.next_state(&drained, AlpenglowAction::AdvanceTime { delta: 1 }).unwrap();
+    let decayed = next_tick.network_state.congestion_state.current_utilization[&(0, 1)];
+    assert!((decayed - remaining * 0.9).abs() < 1e-12);
+    let cleared = drained.next_state(&drained, AlpenglowAction::AdvanceTime { delta: 100 }).unwrap();
+    assert!(cleared.network_state.congestion_state.current_utilization.is_empty());
+    assert_eq!(cleared.calculate_latency(&cleared, 0, 1), 50);
+}
+
+#[test]
+fn test_delayed_flood_creates_congestion() {
+    let mut state = network_test_state(3);
+    state.network_state.packet_loss_rate = 0.0;
+    state.network_state.node_bandwidth.insert(3, 500);
+    let strategy = ByzantineStrategy::CoalitionAttack {
+        coalition_members: vec![3],
+        attack_type: CoalitionAttackType::DelayedFlood { delay_until_slot: 1 },
+    };
+    state.status.insert(3, NodeStatus::Byzantine(strategy.clone()));
+    
+    let flooded = state.next_state(&state, AlpenglowAction::ByzantineVote { node: 3, strategy, slot: 1 }).unwrap();
+    
+    // Four flood votes to each of the three honest nodes
+    assert_eq!(flooded.message_queue.pending_messages.len(), 12);
+    for to in 0..3 {
+        let utilization = flooded.network_state.congestion_state.current_utilization[&(3, to)];
+        assert!(utilization > flooded.network_state.congestion_state.congestion_threshold);
+        assert!(flooded.calculate_latency(&flooded, 3, to) > 50);
+    }
+}
+
+#[test]
+fn test_matrix_latency_follows_regions() {
+    let mut topology = NetworkTopology::from_csv("region,eu,us\neu,4,90\nus,90,4\n").unwrap();
+    topology.assign(0, "eu");
+    topology.assign(1, "eu");
+    topology.assign(2, "us");
+    topology.set_jitter("eu", "us", 5);
+    
+    let mut state = network_test_state(2);
+    state.network_state.latency_model = LatencyModel::Matrix { topology };
+    
+    // Seeded runs draw within the region pair's jitter
+    let sent = broadcast_heartbeats(&state, 2);
+    for msg in &sent.message_queue.pending_messages {
+        let latency = msg.scheduled_delivery_time - msg.send_time;
+        let same_region = (msg.from < 2) == (msg.to < 2);
+        if same_region {
+            assert_eq!(latency, 2);
+        } else {
+            assert!((40..=50).contains(&latency), "{} -> {}: {}", msg.from, msg.to, latency);
+        }
+    }
+    
+    // Nondeterministic runs start at the lowest latency and may add the jitter
+    state.network_state.randomness = NetworkRandomness::Nondeterministic;
+    let sent = send_vote(&state, 0, 2);
+    let message = &sent.message_queue.pending_messages[0];
+    assert_eq!(message.scheduled_delivery_time, 40);
+    assert_eq!(message.latency_slack, 10);
+}
+
+#[test]
+fn test_compact_state_reads_latency_from_sink() {
+    let mut state = network_test_state(2);
+    state.network_state.packet_loss_rate = 0.0;
+    assert_eq!(state.to_compact_state().essential_metrics.average_latency, 0);
+    
+    let sent = broadcast_heartbeats(&state, 2);
+    let delivered = run_network_until(sent, 100);
+    assert!(delivered.message_queue.pending_messages.is_empty());
+    assert_eq!(delivered.to_compact_state().essential_metrics.average_latency, 50);
+    
+    // The history is not part of the state: a fresh sink leaves it unchanged
+    let mut reset = delivered.clone();
+    reset.metrics = MetricsHandle::default();
+    assert_eq!(reset, delivered);
+    assert_eq!(reset.to_compact_state().essential_metrics.average_latency, 0);
+}
+
+/// Every live node heartbeats every other node each `period` until `until`
+fn run_heartbeats_until(mut state: AlpenglowState, until: Timestamp, period: Timestamp) -> AlpenglowState {
+    let model = state.clone();
+    while state.global_time < until {
+        if state.global_time.is_multiple_of(period) {
+            for from in state.nodes.clone() {
+                if matches!(state.status[&from], NodeStatus::Crashed { .. }) {
+                    continue;
+                }
+                for to in state.nodes.clone().into_iter().filter(|&to| to != from) {
+                    state = model.next_state(&state, AlpenglowAction::SendMessage {
+                        from,
+                        to,
+                        content: MessageContent::Heartbeat { sequence: state.global_time },
+                        priority: MessagePriority::Normal,
+                    }).unwrap();
+                }
+            }
+        }
+        state = run_network_until(state.clone(), state.global_time + 1);
+    }
+    state
+}
+
+fn detector_test_state(crashed: Option<NodeId>) -> (AlpenglowState, Arc<Mutex<SuspicionLog>>) {
+    let mut state = network_test_state(2);
+    state.network_state.packet_loss_rate = 0.0;
+    state.failure_detector = Some(FailureDetector::new(100, 0));
+    if let Some(node) = crashed {
+        state.status.insert(node, NodeStatus::Crashed { since: 0 });
+    }
+    let log = Arc::new(Mutex::new(SuspicionLog::default()));
+    state.metrics = MetricsHandle::fan_out(vec![log.clone(), Arc::new(Mutex::new(LatencySummary::default()))]);
+    (state, log)
+}
+
+#[test]
+fn test_failure_detector_suspects_crashed_peer() {
+    let (state, log) = detector_test_state(Some(2));
+    let state = run_heartbeats_until(state, 300, 50);
+    
+    // Live nodes keep hearing from each other and suspect only the crashed one
+    assert!(state.suspects(0, 2) && state.suspects(1, 2));
+    assert!(!state.suspects(0, 1) && !state.suspects(1, 0));
+    // A crashed node runs no detector
+    assert!(!state.suspects(2, 0));
+    // Latency statistics keep running beside the suspicion log
+    assert!(state.metrics.average_latency() > 0);
+    
+    let log = log.lock().unwrap();
+    assert_eq!(log.events.len(), 2);
+    assert!(log.events.iter().all(|event| event.peer == 2 && event.at == 101 && event.last_heard == 0));
+    assert_eq!(log.false_suspicion_rate(), 0.0);
+}
+
+#[test]
+fn test_latency_spike_causes_false_suspicions() {
+    let (mut state, log) = detector_test_state(None);
+    state.load_fault_schedule(FaultSchedule {
+        events: vec![NetworkFailure {
+            failure_type: FailureType::LatencySpike { multiplier: 4.0 },
+            start_time: 100,
+            duration: 200,
+            affected_nodes: vec![],
+            severity: 1.0,
+        }],
+    });
+    
+    // Heartbeats sent during the spike take 200 ms, past the 100 ms timeout
+    let during = run_heartbeats_until(state, 300, 50);
+    assert!(during.suspects(0, 1));
+    {
+        let log = log.lock().unwrap();
+        assert_eq!(log.events.len(), 6);
+        assert!(log.events.iter().all(|event| event.latency_spike && event.peer_alive));
+        assert_eq!(log.false_suspicion_rate_during_spikes(), 1.0);
+    }
+    
+    // Once heartbeats flow normally again, suspicion clears
+    let after = run_heartbeats_until(during, 600, 50);
+    assert!(!after.suspects(0, 1));
+    assert_eq!(after.failure_detector.unwrap().last_heard(0, 1), 550);
+}
+
+#[test]
+fn test_suspected_leader_slot_skipped_early() {
+    let (state, _) = detector_test_state(None);
+    let leader = state.get_leader_for_slot(1);
+    let follower = state.nodes.iter().copied().find(|&node| node != leader).unwrap();
+    
+    // The leader crashes after its last heartbeat at time 0
+    let mut state = run_heartbeats_until(state, 50, 50);
+    state.status.insert(leader, NodeStatus::Crashed { since: 50 });
+    let suspicious = run_heartbeats_until(state, 200, 50);
+    assert!(suspicious.suspects_leader(follower, 1));
+    
+    // A single timeout is enough to be ready to skip the slot
+    let timed_out = suspicious.next_state(&suspicious, AlpenglowAction::Timeout { node: follower, slot: 1 }).unwrap();
+    assert_eq!(timed_out.timeouts[&follower][&1].count, timed_out.timeouts[&follower][&1].threshold);
+    
+    // Without a failure detector the node waits out every timeout
+    let mut unaware = suspicious;
+    unaware.failure_detector = None;
+    let timed_out = unaware.next_state(&unaware, AlpenglowAction::Timeout { node: follower, slot: 1 }).unwrap();
+    assert_eq!(timed_out.timeouts[&follower][&1].count, 1);
+}
+
+fn crash_test_state(budget: f64) -> AlpenglowState {
+    let mut state = network_test_state(9);
+    state.network_state.packet_loss_rate = 0.0;
+    state.crash_budget = budget;
+    state
+}
+
+#[test]
+fn test_crash_budget_limits_crash_actions() {
+    let state = crash_test_state(0.2);
+    let crash_targets = |state: &AlpenglowState| -> Vec<NodeId> {
+        let mut actions = Vec::new();
+        state.actions(state, &mut actions);
+        actions.iter()
+            .filter_map(|action| match action {
+                AlpenglowAction::Crash { node } => Some(*node),
+                _ => None,
+            })
+            .collect()
+    };
+    assert_eq!(crash_targets(&state).len(), 10);
+    assert!(crash_targets(&crash_test_state(0.0)).is_empty());
+    
+    // 20% of 1000 stake allows two crashed validators at a time
+    let mut crashed = state.clone();
+    for node in [0, 1] {
+        crashed = crashed.next_state(&crashed, AlpenglowAction::Crash { node }).unwrap();
+    }
+    assert_eq!(crashed.crashed_stake(), 200);
+    assert!(crash_targets(&crashed).is_empty());
+    let over_budget = crashed.next_state(&crashed, AlpenglowAction::Crash { node: 2 }).unwrap();
+    assert_eq!(over_budget.crashed_stake(), 200);
+    
+    // Recovering one frees budget for another crash
+    let recovered = crashed.next_state(&crashed, AlpenglowAction::Recover { node: 0 }).unwrap();
+    assert_eq!(recovered.status[&0], NodeStatus::Honest);
+    assert_eq!(crash_targets(&recovered).len(), 9);
+}
+
+#[test]
+fn test_recovered_node_cannot_equivocate() {
+    let state = crash_test_state(0.2);
+    let vote = |state: &AlpenglowState, block: BlockId| {
+        state.next_state(state, AlpenglowAction::Vote { node: 1, slot: 1, block, path: VotePath::Fast }).unwrap()
+    };
+    let mut state = run_network_until(send_vote(&vote(&state, 0), 2, 1), 200);
+    assert_eq!(state.votes[&1][&1].len(), 2);
+    
+    // The crash loses what node 1 had received, and messages sent to it while down
+    state = state.next_state(&state, AlpenglowAction::Crash { node: 1 }).unwrap();
+    state = run_network_until(send_vote(&state, 3, 1), 400);
+    assert!(state.votes[&1][&1].is_empty());
+    assert!(vote(&state, 1).votes[&1][&1].is_empty());
+    
+    // After recovery only its own persisted vote is back, and it blocks a conflicting one
+    state = state.next_state(&state, AlpenglowAction::Recover { node: 1 }).unwrap();
+    assert_eq!(state.votes[&1][&1].len(), 1);
+    assert_eq!(state.votes[&1][&1][0].block, 0);
+    let revoted = vote(&state, 1);
+    assert_eq!(revoted.votes[&1][&1], state.votes[&1][&1]);
+    let no_equivocation = state.properties().into_iter().find(|p| p.name == "honest_no_equivocation").unwrap();
+    assert!((no_equivocation.condition)(&revoted, &revoted));
+}
+
+#[test]
+fn test_recovery_restores_votes_for_missing_slots() {
+    let state = crash_test_state(0.2);
+    let vote = |state: &AlpenglowState, block: BlockId| {
+        state.next_state(state, AlpenglowAction::Vote { node: 1, slot: 1, block, path: VotePath::Fast }).unwrap()
+    };
+    let mut state = vote(&state, 0);
+    state = state.next_state(&state, AlpenglowAction::Crash { node: 1 }).unwrap();
+    
+    // The logged vote is restored even when the node has no entry for its slot any more
+    state.votes.remove(&1);
+    state = state.next_state(&state, AlpenglowAction::Recover { node: 1 }).unwrap();
+    assert_eq!(state.votes[&1][&1].len(), 1);
+    assert_eq!(vote(&state, 1).votes[&1][&1], state.votes[&1][&1]);
+}
+
+#[test]
+fn test_liveness_with_byzantine_and_crashed_stake() {
+    let mut state = crash_test_state(0.2);
+    for node in [0, 1] {
+        state.status.insert(node, NodeStatus::Byzantine(ByzantineStrategy::WithholdVotes));
+    }
+    for node in [2, 3] {
+        state = state.next_state(&state, AlpenglowAction::Crash { node }).unwrap();
+    }
+    let holds = |state: &AlpenglowState, name: &str| {
+        let property = state.properties().into_iter().find(|p| p.name == name).unwrap();
+        (property.condition)(state, state)
+    };
+    
+    // 20% Byzantine and 20% crashed leaves exactly the 60% slow quorum live and honest, but
+    // the Byzantine bound is strict, so this is already past the thresholds
+    assert_eq!(state.honest_stake(), state.slow_quorum_stake());
+    assert_eq!(state.crash_threshold_stake(), state.byzantine_threshold_stake());
+    assert!(!state.faults_within_thresholds());
+    assert!(state.honest_certification_completes(1, VotePath::Slow));
+    
+    // With 10% Byzantine the faults are within the thresholds and the liveness properties apply
+    let mut within = state.clone();
+    within.status.insert(1, NodeStatus::Honest);
+    assert!(within.faults_within_thresholds());
+    assert!(within.honest_certification_completes(1, VotePath::Slow));
+    assert!(!within.honest_certification_completes(1, VotePath::Fast));
+    assert!(within.honest_skip_completes(1));
+    assert!(holds(&within, "crash_fault_liveness"));
+    assert!(holds(&within, "crash_fault_skip_liveness"));
+    
+    // One more crash past the threshold and the live honest nodes can do neither
+    let mut overloaded = state.clone();
+    overloaded.status.insert(4, NodeStatus::Crashed { since: 0 });
+    assert!(!overloaded.faults_within_thresholds());
+    assert!(!overloaded.honest_certification_completes(1, VotePath::Slow));
+    assert!(!overloaded.honest_skip_completes(1));
+    
+    // The six live honest nodes notarize on their own
+    for node in 4..=9 {
+        state = state.next_state(&state, AlpenglowAction::Vote { node, slot: 1, block: 0, path: VotePath::Slow }).unwrap();
+    }
+    state = state.next_state(&state, AlpenglowAction::Certify { slot: 1, path: VotePath::Slow }).unwrap();
+    assert_eq!(state.certificates[&1].total_stake, 600);
+    
+    // The budget keeps a third crash, which would leave the quorum out of reach, from happening
+    assert!(!state.can_crash(4));
+}
@@ -6,7 +6,7 @@ use std::collections::HashMap;
 #[test]
 fn test_basic_scalability_properties() {
     // Test properties that should hold for large networks
-    for network_size in vec![10, 25, 50, 100] {
+    for network_size in [10, 25, 50, 100] {
         let mut nodes = vec![];
         let mut stake_map = HashMap::new();
         
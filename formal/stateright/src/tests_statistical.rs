@@ -315,7 +315,7 @@ fn test_statistical_result_properties() {
 #[test]
 fn test_basic_scalability_properties() {
     // Test properties that should hold for large networks
-    for network_size in vec![20, 50, 100] {
+    for network_size in [20, 50, 100] {
         let mut nodes = vec![];
         let mut stake_map = HashMap::new();
         